@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+use poker::{showdown_hands, showdown_values, BuildingHand, Card, Deck, ShowdownHand};
+use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, SeedableRng};
+
+const HANDS: usize = 1_000_000;
+const RUNS: usize = 5;
+
+// fastest of a few runs, in nanoseconds per hand
+fn time(mut run: impl FnMut()) -> f64 {
+    let best = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::default());
+    best.as_nanos() as f64 / HANDS as f64
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(41);
+    let mut deck: Vec<Card> = Deck::new().cards();
+    let hands: Vec<BuildingHand> = (0..HANDS)
+        .map(|_| {
+            deck.shuffle(&mut rng);
+            let mut hand = BuildingHand::new();
+            for card in &deck[..7] {
+                hand.add_card(card, false);
+            }
+            hand
+        })
+        .collect();
+    let bits: Vec<u64> = hands.iter()
+        .map(|hand| hand.cards().into_iter().fold(0, |bits, card| bits | card as u64))
+        .collect();
+
+    let mut showdowns = vec![ShowdownHand::default(); HANDS];
+    let single = time(|| {
+        for (out, hand) in showdowns.iter_mut().zip(&hands) {
+            *out = hand.to_showdown_hand();
+        }
+    });
+    let batch = time(|| {
+        showdown_hands(&hands, &mut showdowns);
+    });
+    let mut values = vec![0; HANDS];
+    let raw = time(|| {
+        showdown_values(&bits, &mut values);
+    });
+
+    println!("{} random 7 card hands, best of {} runs", HANDS, RUNS);
+    println!("to_showdown_hand  {:>7.1} ns/hand", single);
+    println!("showdown_hands    {:>7.1} ns/hand  {:>5.1}x", batch, single / batch);
+    println!("showdown_values   {:>7.1} ns/hand  {:>5.1}x", raw, single / raw);
+}
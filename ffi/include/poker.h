@@ -0,0 +1,81 @@
+#ifndef POKER_H
+#define POKER_H
+
+/* Generated with cbindgen from ffi/src/lib.rs, don't edit by hand: cbindgen -o include/poker.h */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define POKER_OK 0
+
+#define POKER_INVALID_CARD -1
+
+#define POKER_DUPLICATE_CARD -2
+
+#define POKER_INVALID_ARGUMENT -3
+
+#define POKER_HAND_CLASSES 7462
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Parses a card like "As" into its card bit.
+
+ # Safety
+ `card` is a NUL terminated string and `out` points to a u64, or either is NULL.
+ */
+int32_t poker_parse_card(const char *card, uint64_t *out);
+
+/*
+ Parses cards like "As Ks Qs", spaces optional, into hand bits.
+
+ # Safety
+ `cards` is a NUL terminated string and `out` points to a u64, or either is NULL.
+ */
+int32_t poker_parse_hand(const char *cards, uint64_t *out);
+
+/*
+ Showdown value of the best five cards of the hand, the greater value wins and equal values
+ tie. The hand class, 1 for high card up to 9 for a straight flush and 0 without cards, is
+ `value >> 28`.
+ */
+uint32_t poker_evaluate(uint64_t hand);
+
+/*
+ Showdown values of `n` hands into `out`, same as poker_evaluate on each.
+
+ # Safety
+ `hands` and `out` point to `n` values each, or either is NULL.
+ */
+int32_t poker_evaluate_batch(const uint64_t *hands, size_t n, uint32_t *out);
+
+/*
+ Class of the best five cards among the 7462 distinct five card hands, 1 for a royal flush
+ down to POKER_HAND_CLASSES for 7-5-4-3-2 offsuit, 0 for hands of fewer than five cards.
+ */
+uint32_t poker_hand_class(uint64_t hand);
+
+/*
+ Exact all-in equity of `n` two card hold'em hands over every board that completes `board`,
+ without the `dead` cards, each hand's share of the pot into `equities`. A card in more than
+ one of the hands, board and dead cards is POKER_DUPLICATE_CARD.
+
+ # Safety
+ `hands` and `equities` point to `n` values each, or either is NULL.
+ */
+int32_t poker_equity(const uint64_t *hands,
+                     size_t n,
+                     uint64_t board,
+                     uint64_t dead,
+                     double *equities);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* POKER_H */
@@ -0,0 +1,127 @@
+//! C ABI of the poker evaluator, see include/poker.h, generated with `cbindgen -o include/poker.h`.
+//!
+//! Hands cross the ABI as the raw u64 card bits of a BuildingHand, card bit 4 * rank + suit with
+//! ranks from 0 for deuces to 12 for aces and suits diamonds, clubs, hearts, spades.
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::slice;
+
+use poker::{equity, showdown_values, BuildingHand, Card, ErrorKind};
+
+pub const POKER_OK: i32 = 0;
+pub const POKER_INVALID_CARD: i32 = -1;
+pub const POKER_DUPLICATE_CARD: i32 = -2;
+pub const POKER_INVALID_ARGUMENT: i32 = -3;
+
+pub const POKER_HAND_CLASSES: u32 = 7462;
+
+/// Parses a card like "As" into its card bit.
+///
+/// # Safety
+/// `card` is a NUL terminated string and `out` points to a u64, or either is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn poker_parse_card(card: *const c_char, out: *mut u64) -> i32 {
+    let card = match string(card) {
+        Some(card) if !out.is_null() => card,
+        _ => return POKER_INVALID_ARGUMENT,
+    };
+    match card.parse::<Card>() {
+        Ok(card) => {
+            *out = card as u64;
+            POKER_OK
+        },
+        Err(_) => POKER_INVALID_CARD,
+    }
+}
+
+/// Parses cards like "As Ks Qs", spaces optional, into hand bits.
+///
+/// # Safety
+/// `cards` is a NUL terminated string and `out` points to a u64, or either is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn poker_parse_hand(cards: *const c_char, out: *mut u64) -> i32 {
+    let cards = match string(cards) {
+        Some(cards) if !out.is_null() => cards,
+        _ => return POKER_INVALID_ARGUMENT,
+    };
+    match cards.parse::<BuildingHand>() {
+        Ok(hand) => {
+            *out = hand.bits();
+            POKER_OK
+        },
+        Err(e) if e.kind == ErrorKind::DuplicateCard => POKER_DUPLICATE_CARD,
+        Err(_) => POKER_INVALID_CARD,
+    }
+}
+
+/// Showdown value of the best five cards of the hand, the greater value wins and equal values
+/// tie. The hand class, 1 for high card up to 9 for a straight flush and 0 without cards, is
+/// `value >> 28`.
+#[no_mangle]
+pub extern "C" fn poker_evaluate(hand: u64) -> u32 {
+    BuildingHand::from_bits(hand).to_showdown_hand().value()
+}
+
+/// Showdown values of `n` hands into `out`, same as poker_evaluate on each.
+///
+/// # Safety
+/// `hands` and `out` point to `n` values each, or either is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn poker_evaluate_batch(hands: *const u64, n: usize, out: *mut u32) -> i32 {
+    if hands.is_null() || out.is_null() {
+        return POKER_INVALID_ARGUMENT;
+    }
+    match showdown_values(slice::from_raw_parts(hands, n), slice::from_raw_parts_mut(out, n)) {
+        None => POKER_OK,
+        Some(_) => POKER_INVALID_ARGUMENT,
+    }
+}
+
+/// Class of the best five cards among the 7462 distinct five card hands, 1 for a royal flush
+/// down to POKER_HAND_CLASSES for 7-5-4-3-2 offsuit, 0 for hands of fewer than five cards.
+#[no_mangle]
+pub extern "C" fn poker_hand_class(hand: u64) -> u32 {
+    BuildingHand::from_bits(hand).to_showdown_hand().class().unwrap_or(0) as u32
+}
+
+/// Exact all-in equity of `n` two card hold'em hands over every board that completes `board`,
+/// without the `dead` cards, each hand's share of the pot into `equities`. A card in more than
+/// one of the hands, board and dead cards is POKER_DUPLICATE_CARD.
+///
+/// # Safety
+/// `hands` and `equities` point to `n` values each, or either is NULL.
+#[no_mangle]
+pub unsafe extern "C" fn poker_equity(hands: *const u64, n: usize, board: u64, dead: u64, equities: *mut f64) -> i32 {
+    if hands.is_null() || equities.is_null() {
+        return POKER_INVALID_ARGUMENT;
+    }
+    let hands: Vec<BuildingHand> = slice::from_raw_parts(hands, n).iter().map(|&hand| BuildingHand::from_bits(hand)).collect();
+    match equity(&hands, &BuildingHand::from_bits(board), &BuildingHand::from_bits(dead)) {
+        Ok(results) => {
+            for (out, result) in slice::from_raw_parts_mut(equities, n).iter_mut().zip(results) {
+                *out = result.equity;
+            }
+            POKER_OK
+        },
+        Err(e) if e.kind == ErrorKind::DuplicateCard => POKER_DUPLICATE_CARD,
+        Err(_) => POKER_INVALID_ARGUMENT,
+    }
+}
+
+unsafe fn string<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+    CStr::from_ptr(string).to_str().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_classes_as_the_evaluator() {
+        assert_eq!(poker::HAND_CLASSES, POKER_HAND_CLASSES as usize);
+    }
+}
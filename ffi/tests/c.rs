@@ -0,0 +1,35 @@
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+// compiles tests/poker_test.c against the static library and runs it
+#[test]
+fn c_test_program() {
+    // target/<profile>/deps/c-<hash>, the library is in target/<profile>
+    let exe = env::current_exe().unwrap();
+    let profile = exe.parent().and_then(|deps| deps.parent()).unwrap();
+    let manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let program = profile.join("poker_test");
+
+    // cargo test only builds the rust library, not the static one
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "-p", "poker-ffi"])
+        .args(if profile.ends_with("release") { &["--release"][..] } else { &[] })
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_owned()))
+        .arg(manifest.join("tests/poker_test.c"))
+        .arg("-I").arg(manifest.join("include"))
+        .arg(profile.join("libpoker_ffi.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .expect("Failed to run the C compiler");
+    assert!(status.success());
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!("ok\n", String::from_utf8_lossy(&output.stdout));
+}
@@ -0,0 +1,58 @@
+/* Links against the static library and checks the C API end to end, run by tests/c.rs */
+
+#include <math.h>
+#include <stdio.h>
+
+#include "poker.h"
+
+static int failures = 0;
+
+#define CHECK(condition) do { \
+    if (!(condition)) { \
+        fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+        failures++; \
+    } \
+} while (0)
+
+int main(void) {
+    uint64_t card = 0, royal = 0, quads = 0, board = 0, hands[2] = {0};
+
+    CHECK(poker_parse_card("As", &card) == POKER_OK);
+    CHECK(card == 1ULL << 51);
+    CHECK(poker_parse_card("Ax", &card) == POKER_INVALID_CARD);
+    CHECK(poker_parse_card(NULL, &card) == POKER_INVALID_ARGUMENT);
+
+    CHECK(poker_parse_hand("As Ks Qs Js Ts", &royal) == POKER_OK);
+    CHECK(poker_parse_hand("AdAcAhAs2c", &quads) == POKER_OK);
+    CHECK(poker_parse_hand("As Ks As", &card) == POKER_DUPLICATE_CARD);
+    CHECK(poker_parse_hand("As K", &card) == POKER_INVALID_CARD);
+    CHECK(poker_parse_hand("AsKs As", &card) == POKER_DUPLICATE_CARD);
+    CHECK(poker_parse_hand("As Xs", &card) == POKER_INVALID_CARD);
+
+    CHECK(poker_evaluate(royal) > poker_evaluate(quads));
+    CHECK(poker_evaluate(royal) >> 28 == 9);
+    CHECK(poker_hand_class(royal) == 1);
+    CHECK(poker_hand_class(quads) == 22);
+    CHECK(poker_hand_class(card) == 0);
+
+    uint64_t batch[2] = {royal, quads};
+    uint32_t values[2] = {0};
+    CHECK(poker_evaluate_batch(batch, 2, values) == POKER_OK);
+    CHECK(values[0] == poker_evaluate(royal) && values[1] == poker_evaluate(quads));
+
+    /* a flush draw against an overpair, every river */
+    double equities[2] = {0};
+    CHECK(poker_parse_hand("Ah Kh", &hands[0]) == POKER_OK);
+    CHECK(poker_parse_hand("Qs Qd", &hands[1]) == POKER_OK);
+    CHECK(poker_parse_hand("7h 5h 2c 9d", &board) == POKER_OK);
+    CHECK(poker_equity(hands, 2, board, 0, equities) == POKER_OK);
+    CHECK(fabs(equities[0] - 15.0 / 44.0) < 1e-9);
+    CHECK(fabs(equities[0] + equities[1] - 1.0) < 1e-9);
+    CHECK(poker_equity(hands, 1, board, 0, equities) == POKER_INVALID_ARGUMENT);
+    CHECK(poker_equity(hands, 2, board, hands[0], equities) == POKER_DUPLICATE_CARD);
+
+    if (failures == 0) {
+        printf("ok\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,263 @@
+use crate::{check_disjoint, rank_set, wheel_rank_set, BuildingHand, Error, Order, SUIT_MASKS};
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum MadeHand {
+    Nothing, // no pair of our own, the board may still be paired
+    Underpair,
+    PocketPair, // pocket pair between the top and bottom board cards
+    BottomPair,
+    MiddlePair,
+    TopPair,
+    TopPairTopKicker,
+    Overpair,
+    TwoPair,
+    Trips, // one hole card plus a pair on board
+    Set,   // pocket pair plus one on board
+    Straight,
+    Flush,
+    FullHouse,
+    Quads,
+    StraightFlush,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Draw {
+    FlushDraw,
+    NutFlushDraw,
+    OpenEnded,
+    Gutshot,
+    DoubleGutter,
+    BackdoorFlush,
+    BackdoorStraight,
+    Combo, // flush draw and straight draw together
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Analysis {
+    pub order: Order,
+    pub made: MadeHand,
+    pub draws: Vec<Draw>,
+}
+
+/// Made hand and draws of two hole cards on a flop or turn board.
+pub fn analyze(hole: &BuildingHand, board: &BuildingHand) -> Result<Analysis, Error> {
+    if hole.len() != 2 {
+        return Err(Error::of(format!("Invalid hole cards, 2 required, {} given", hole.len())));
+    }
+    if board.len() != 3 && board.len() != 4 {
+        return Err(Error::of(format!("Invalid board, 3 or 4 cards required, {} given", board.len())));
+    }
+    check_disjoint(hole.0, board.0)?;
+
+    let order = BuildingHand(hole.0 | board.0).to_realized_hand().order();
+    let made = made_hand(hole, board, &order);
+
+    let mut draws = Vec::new();
+    if order < Order::Flsh {
+        draws.extend(flush_draws(hole, board));
+    }
+    if order < Order::Strt {
+        draws.extend(straight_draws(hole, board));
+    }
+
+    let flush_draw = draws.contains(&Draw::FlushDraw) || draws.contains(&Draw::NutFlushDraw);
+    let straight_draw = draws.iter().any(|draw| [Draw::OpenEnded, Draw::Gutshot, Draw::DoubleGutter].contains(draw));
+    if flush_draw && straight_draw {
+        draws.push(Draw::Combo);
+    }
+
+    Ok(Analysis { order, made, draws })
+}
+
+fn made_hand(hole: &BuildingHand, board: &BuildingHand, order: &Order) -> MadeHand {
+    let count = |cards: u64, rank: u32| ((cards >> ((rank - 1) << 2)) & 0xf).count_ones();
+    let hole_ranks = rank_set(hole.0);
+    let board_ranks = rank_set(board.0);
+    let pocket_pair = hole_ranks.count_ones() == 1;
+
+    // board ranks from the top down, and hole ranks that pair one of them
+    let board_desc: Vec<u32> = (1..14).rev().filter(|rank| board_ranks & (1 << (rank - 1)) != 0).collect();
+    let paired: Vec<u32> = board_desc.iter().cloned().filter(|rank| hole_ranks & (1 << (rank - 1)) != 0).collect();
+
+    match order {
+        Order::Stfl | Order::Fivk => MadeHand::StraightFlush,
+        Order::Quad => MadeHand::Quads,
+        Order::Boat => MadeHand::FullHouse,
+        Order::Flsh => MadeHand::Flush,
+        Order::Strt => MadeHand::Straight,
+        Order::Trip if pocket_pair && !paired.is_empty() => MadeHand::Set,
+        Order::Trip if !paired.is_empty() => MadeHand::Trips,
+        Order::Twop if paired.len() == 2 => MadeHand::TwoPair,
+        Order::Twop | Order::Pair if pocket_pair => {
+            let rank = 32 - hole_ranks.leading_zeros();
+            if rank > board_desc[0] {
+                MadeHand::Overpair
+            } else if rank < board_desc[board_desc.len() - 1] {
+                MadeHand::Underpair
+            } else {
+                MadeHand::PocketPair
+            }
+        },
+        Order::Twop | Order::Pair if paired.len() == 1 => {
+            let rank = paired[0];
+            if rank == board_desc[0] {
+                // best kicker is the highest rank nobody can pair from the board
+                let top_kicker = (1..14).rev().find(|kicker| board_ranks & (1 << (kicker - 1)) == 0).unwrap_or(0);
+                let kicker = 32 - (hole_ranks & !(1 << (rank - 1))).leading_zeros();
+                if kicker == top_kicker && count(hole.0, rank) == 1 { MadeHand::TopPairTopKicker } else { MadeHand::TopPair }
+            } else if rank == board_desc[board_desc.len() - 1] {
+                MadeHand::BottomPair
+            } else {
+                MadeHand::MiddlePair
+            }
+        },
+        _ => MadeHand::Nothing,
+    }
+}
+
+fn flush_draws(hole: &BuildingHand, board: &BuildingHand) -> Vec<Draw> {
+    let mut draws = Vec::new();
+    for (mask, _) in &SUIT_MASKS {
+        let mask = mask.clone() as u64;
+        let hole_suited = hole.0 & mask;
+        let board_suited = board.0 & mask;
+        if hole_suited == 0 {
+            continue;
+        }
+
+        match (hole_suited | board_suited).count_ones() {
+            4 => {
+                // the nut card is the highest one of the suit that's not on the board
+                let nut_card = (0..13).rev()
+                    .map(|i| mask & (0xf << (i << 2)))
+                    .find(|card| board_suited & card == 0)
+                    .unwrap_or(0);
+                draws.push(if hole_suited & nut_card != 0 { Draw::NutFlushDraw } else { Draw::FlushDraw });
+            },
+            3 if board.len() == 3 => draws.push(Draw::BackdoorFlush),
+            _ => {},
+        }
+    }
+    draws
+}
+
+fn straight_draws(hole: &BuildingHand, board: &BuildingHand) -> Vec<Draw> {
+    let ranks = wheel_rank_set(hole.0 | board.0);
+    let board_ranks = wheel_rank_set(board.0);
+    let hole_ranks = wheel_rank_set(hole.0);
+
+    // every rank that would complete a straight using at least one hole card of a rank the board lacks
+    let mut completing = 0u32;
+    let mut backdoor = false;
+    for low in 0..10 {
+        let window = 0b11111 << low;
+        if window & hole_ranks & !board_ranks == 0 {
+            continue;
+        }
+        match (ranks & window).count_ones() {
+            4 => completing |= window & !ranks,
+            3 => backdoor = true,
+            _ => {},
+        }
+    }
+
+    // both ace bits are the same card, so the low one joins the high one
+    let completing = completing >> 1 | (completing & 1) << 12;
+    let four_in_a_row = (0..10).any(|low| (ranks >> low) & 0b1111 == 0b1111 && low > 0 && low + 4 < 14);
+
+    match completing.count_ones() {
+        0 if backdoor && board.len() == 3 => vec![Draw::BackdoorStraight],
+        0 => vec![],
+        1 => vec![Draw::Gutshot],
+        _ if four_in_a_row => vec![Draw::OpenEnded],
+        _ => vec![Draw::DoubleGutter],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, ErrorKind};
+
+    #[test]
+    fn invalid_input() {
+        let board = hand!("Kd 7c 2h");
+        assert!(analyze(&hand!("As"), &board).is_err());
+        assert!(analyze(&hand!("As Ks"), &hand!("Kd 7c")).is_err());
+        assert_eq!(ErrorKind::DuplicateCard, analyze(&hand!("As Kd"), &board).unwrap_err().kind);
+    }
+
+    #[test]
+    fn made_hands_relative_to_board() {
+        let board = hand!("Kd 7c 2h");
+        let made = |hole: BuildingHand| analyze(&hole, &board).unwrap().made;
+        assert_eq!(MadeHand::TopPairTopKicker, made(hand!("Ks As")));
+        assert_eq!(MadeHand::TopPair, made(hand!("Ks Qs")));
+        assert_eq!(MadeHand::MiddlePair, made(hand!("7s As")));
+        assert_eq!(MadeHand::BottomPair, made(hand!("2s As")));
+        assert_eq!(MadeHand::Overpair, made(hand!("As Ah")));
+        assert_eq!(MadeHand::PocketPair, made(hand!("9s 9h")));
+        assert_eq!(MadeHand::TwoPair, made(hand!("Ks 7h")));
+        assert_eq!(MadeHand::Set, made(hand!("7s 7h")));
+        assert_eq!(MadeHand::Nothing, made(hand!("As Qh")));
+
+        let paired_board = hand!("Kd 7c 7h 3d");
+        let made = |hole: BuildingHand| analyze(&hole, &paired_board).unwrap().made;
+        assert_eq!(MadeHand::Trips, made(hand!("7s As")));
+        assert_eq!(MadeHand::Underpair, made(hand!("2s 2h")));
+        assert_eq!(MadeHand::TopPair, made(hand!("Ks Qh")));
+        assert_eq!(MadeHand::Nothing, made(hand!("As Qh")));
+    }
+
+    #[test]
+    fn flush_draws() {
+        let board = hand!("Ks 7s 2h");
+        let draws = |hole: BuildingHand| analyze(&hole, &board).unwrap().draws;
+        assert_eq!(vec![Draw::NutFlushDraw], draws(hand!("As 9s")));
+        assert_eq!(vec![Draw::FlushDraw], draws(hand!("9s 4s")));
+        assert_eq!(vec![Draw::FlushDraw, Draw::BackdoorStraight], draws(hand!("Qs Js")));
+        assert_eq!(vec![Draw::BackdoorFlush, Draw::BackdoorStraight], draws(hand!("Qh Jh")));
+
+        // no backdoors on the turn
+        let turn = hand!("Ks 7s 2h 3c");
+        assert_eq!(Vec::<Draw>::new(), analyze(&hand!("Qh 9h"), &turn).unwrap().draws);
+    }
+
+    #[test]
+    fn straight_draws() {
+        let board = hand!("8d 7c 2h");
+        let draws = |hole: BuildingHand| analyze(&hole, &board).unwrap().draws;
+        assert_eq!(vec![Draw::OpenEnded], draws(hand!("9s 6d")));
+        assert_eq!(vec![Draw::Gutshot], draws(hand!("5s 4d")));
+        assert_eq!(vec![Draw::Gutshot], draws(hand!("Ts 6d")));
+        assert_eq!(vec![Draw::BackdoorStraight], draws(hand!("Qs 9d")));
+
+        let board = hand!("9d 7c 5h");
+        let draws = |hole: BuildingHand| analyze(&hole, &board).unwrap().draws;
+        assert_eq!(vec![Draw::DoubleGutter], draws(hand!("Js 8d")));
+
+        // only one card completes a straight to the ace
+        let board = hand!("Kd Qc 2h");
+        assert_eq!(vec![Draw::Gutshot], analyze(&hand!("As Jd"), &board).unwrap().draws);
+
+        // the board's own draw isn't the player's when the hole card only pairs it
+        let board = hand!("9h 8d 7s 6c");
+        assert_eq!(Vec::<Draw>::new(), analyze(&hand!("9c 2d"), &board).unwrap().draws);
+    }
+
+    #[test]
+    fn combo_draw() {
+        let board = hand!("8s 7s 2h");
+        let analysis = analyze(&hand!("9s 6s"), &board).unwrap();
+        assert_eq!(vec![Draw::FlushDraw, Draw::OpenEnded, Draw::Combo], analysis.draws);
+        assert_eq!(MadeHand::Nothing, analysis.made);
+    }
+
+    #[test]
+    fn made_hands_have_no_draws_to_the_same_class() {
+        let board = hand!("8s 7s 6s 5h");
+        let analysis = analyze(&hand!("9d As"), &board).unwrap();
+        assert_eq!(MadeHand::Straight, analysis.made);
+        assert_eq!(vec![Draw::NutFlushDraw], analysis.draws);
+    }
+}
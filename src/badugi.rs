@@ -0,0 +1,143 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::{capitalize, rank_set, BuildingHand, Rank, SUIT_MASKS};
+
+/* |............|...|.|....|....|....|....|
+ * - 12 bits: TBD
+ * -  3 bits: number of cards played, distinct in both rank and suit
+ * -  1 bit:  TBD
+ * - 4x4 bits: card ranks in descending order from msb to lsb, aces are 1
+ *
+ * More cards always win, ties between equal sizes go to the lower ranks.
+ */
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BadugiHand(u32);
+
+impl BuildingHand {
+    pub fn to_badugi_hand(&self) -> BadugiHand {
+        for size in (1..=self.len().min(4)).rev() {
+            let best = self.combinations(size)
+                .iter()
+                .filter(|hand| is_badugi(hand))
+                .map(BadugiHand::of)
+                .max();
+
+            if let Some(best) = best {
+                return best;
+            }
+        }
+        BadugiHand(0)
+    }
+}
+
+// one card per suit and one card per rank
+fn is_badugi(hand: &BuildingHand) -> bool {
+    let suits = SUIT_MASKS.iter()
+        .filter(|(mask, _)| hand.0 & (mask.clone() as u64) != 0)
+        .count();
+    suits == hand.len() && rank_set(hand.0).count_ones() as usize == hand.len()
+}
+
+impl BadugiHand {
+    fn of(hand: &BuildingHand) -> BadugiHand {
+        let mut ranks: Vec<u32> = hand.cards()
+            .iter()
+            .map(|card| if card.rank() == Rank::_A { 1 } else { card.rank() as u32 + 1 })
+            .collect();
+        ranks.sort_unstable_by(|a, b| b.cmp(a));
+
+        let ranks = ranks.iter()
+            .chain([0u32; 4].iter())
+            .take(4)
+            .fold(0, |acc, rank| acc << 4 | rank);
+        BadugiHand((hand.len() as u32) << 17 | ranks)
+    }
+
+    /// Number of cards that play, four for a complete badugi.
+    pub fn len(&self) -> usize {
+        (self.0 >> 17 & 0b111) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_badugi(&self) -> bool {
+        self.len() == 4
+    }
+
+    /// The ranks that play, highest first.
+    pub fn ranks(&self) -> Vec<Rank> {
+        (0..self.len())
+            .map(|i| (self.0 >> (12 - 4 * i)) & 0xf)
+            .map(|rank| if rank == 1 { Rank::_A } else { Rank::from_bits(rank as u8 - 1) })
+            .collect()
+    }
+}
+
+impl PartialOrd for BadugiHand {
+    fn partial_cmp(&self, other: &BadugiHand) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BadugiHand {
+    fn cmp(&self, other: &BadugiHand) -> Ordering {
+        self.len().cmp(&other.len())
+            .then_with(|| (other.0 & 0xffff).cmp(&(self.0 & 0xffff)))
+    }
+}
+
+impl fmt::Display for BadugiHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ranks: Vec<_> = self.ranks().iter().map(|rank| rank.name()).collect();
+        match self.len() {
+            0 => write!(f, "No hand"),
+            4 => write!(f, "{} badugi", capitalize(&ranks.join("-"))),
+            n => write!(f, "{}-card {}", ["One", "Two", "Three"][n - 1], ranks.join("-")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand;
+
+    #[test]
+    fn four_card_badugi() {
+        let badugi = hand!("7d 5c 3h As").to_badugi_hand();
+        assert!(badugi.is_badugi());
+        assert_eq!(vec![Rank::_7, Rank::_5, Rank::_3, Rank::_A], badugi.ranks());
+        assert_eq!("Seven-five-three-ace badugi", badugi.to_string());
+
+        let worse = hand!("8d 4c 3h 2s").to_badugi_hand();
+        assert!(badugi > worse);
+    }
+
+    #[test]
+    fn paired_and_suited_cards_are_dropped() {
+        // the two clubs can't both play, so keep the lower one
+        let three = hand!("Kc 4c 2d 6h").to_badugi_hand();
+        assert_eq!(3, three.len());
+        assert_eq!(vec![Rank::_6, Rank::_4, Rank::_2], three.ranks());
+        assert_eq!("Three-card six-four-two", three.to_string());
+
+        // the two aces can't both play either
+        let two = hand!("Ac Ad Kc Kd").to_badugi_hand();
+        assert_eq!(vec![Rank::_K, Rank::_A], two.ranks());
+        assert_eq!("Two-card king-ace", two.to_string());
+
+        let worst_badugi = hand!("Kc Qd Jh Ts").to_badugi_hand();
+        assert!(worst_badugi > three);
+        assert!(three > two);
+    }
+
+    #[test]
+    fn one_card_hand() {
+        let one = hand!("5s 7s 9s Ks").to_badugi_hand();
+        assert_eq!(vec![Rank::_5], one.ranks());
+        assert!(BuildingHand::new().to_badugi_hand().is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{rank_counts, suit_ranks, BuildingHand, Error, ShowdownHand, SUITS};
+
+// hands per pass, small enough for every lane to stay in L1
+const CHUNK: usize = 64;
+
+/// Showdown hands of many hands at once, `out[i]` for `hands[i]`, same as BuildingHand::to_showdown_hand.
+pub fn showdown_hands(hands: &[BuildingHand], out: &mut [ShowdownHand]) -> Option<Error> {
+    if hands.len() != out.len() {
+        return Some(error!("Invalid output", "{} hands but room for {}", hands.len(), out.len()));
+    }
+
+    let mut lanes = Lanes::new();
+    for (hands, out) in hands.chunks(CHUNK).zip(out.chunks_mut(CHUNK)) {
+        lanes.load(hands.iter().map(|hand| hand.0));
+        for (lane, out) in out.iter_mut().enumerate() {
+            *out = lanes.showdown_hand(lane);
+        }
+    }
+    None
+}
+
+/// Showdown values of many hands of raw card bits at once, see ShowdownHand::value.
+pub fn showdown_values(hands: &[u64], out: &mut [u32]) -> Option<Error> {
+    if hands.len() != out.len() {
+        return Some(error!("Invalid output", "{} hands but room for {}", hands.len(), out.len()));
+    }
+
+    let mut lanes = Lanes::new();
+    for (hands, out) in hands.chunks(CHUNK).zip(out.chunks_mut(CHUNK)) {
+        lanes.load(hands.iter().cloned());
+        for (lane, out) in out.iter_mut().enumerate() {
+            *out = lanes.showdown_hand(lane).value();
+        }
+    }
+    None
+}
+
+// one pass of hands, an array per mask with a lane per hand, so the suit and count passes are
+// branch free loops over fixed length arrays the compiler can vectorize, then every hand goes
+// through the same classification as single hands
+struct Lanes {
+    cards: [u64; CHUNK],
+    suits: [[u16; CHUNK]; 4],
+    counts: [[u16; CHUNK]; 4],
+}
+
+impl Lanes {
+    fn new() -> Lanes {
+        Lanes { cards: [0; CHUNK], suits: [[0; CHUNK]; 4], counts: [[0; CHUNK]; 4] }
+    }
+
+    // lanes past the last hand of a short pass are empty hands
+    fn load(&mut self, hands: impl Iterator<Item = u64>) {
+        let Lanes { cards, suits, counts } = self;
+        *cards = [0; CHUNK];
+        for (lane, hand) in cards.iter_mut().zip(hands) {
+            *lane = hand;
+        }
+
+        for (suit, suits) in SUITS.iter().zip(suits.iter_mut()) {
+            for (lane, &cards) in suits.iter_mut().zip(cards.iter()) {
+                *lane = suit_ranks(cards, suit) as u16;
+            }
+        }
+
+        let [d, c, h, s] = suits;
+        for lane in 0..CHUNK {
+            let lane_counts = rank_counts(&[d[lane], c[lane], h[lane], s[lane]]);
+            for (counts, count) in counts.iter_mut().zip(lane_counts) {
+                counts[lane] = count;
+            }
+        }
+    }
+
+    fn showdown_hand(&self, lane: usize) -> ShowdownHand {
+        let [d, c, h, s] = &self.suits;
+        let [ranks, pairs, trips, quads] = &self.counts;
+        let suits = [d[lane], c[lane], h[lane], s[lane]];
+        let counts = [ranks[lane], pairs[lane], trips[lane], quads[lane]];
+        BuildingHand::from_bits(self.cards[lane]).classify(&suits, &counts).to_showdown_hand()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Card;
+    #[cfg(feature = "std")]
+    use crate::CARDS;
+    #[cfg(feature = "std")]
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn same_as_single_hands() {
+        let mut rng = StdRng::seed_from_u64(41);
+        let mut deck = CARDS.to_vec();
+        let hands: Vec<BuildingHand> = (0..3_000)
+            .map(|i| {
+                deck.shuffle(&mut rng);
+                BuildingHand(deck[..i % 10].iter().fold(0, |hand, card| hand | card.clone() as u64))
+            })
+            .collect();
+
+        let mut out = vec![ShowdownHand::default(); hands.len()];
+        assert_eq!(None, showdown_hands(&hands, &mut out));
+        for (hand, showdown) in hands.iter().zip(&out) {
+            // raw bits, so the flush suit matches too
+            assert_eq!(hand.to_showdown_hand().0, showdown.0, "{:?}", hand.cards());
+        }
+
+        let bits: Vec<u64> = hands.iter().map(|hand| hand.0).collect();
+        let mut values = vec![0; bits.len()];
+        assert_eq!(None, showdown_values(&bits, &mut values));
+        for (showdown, value) in out.iter().zip(&values) {
+            assert_eq!(showdown.value(), *value);
+        }
+    }
+
+    #[test]
+    fn values_compare_like_showdown_hands() {
+        let bits = |cards: &[Card]| cards.iter().fold(0, |hand, card| hand | card.clone() as u64);
+        let hands = [
+            bits(&[Card::_Ad, Card::_Jd, Card::_8d, Card::_4d, Card::_2d]),
+            bits(&[Card::_As, Card::_Js, Card::_8s, Card::_4s, Card::_2s]),
+            bits(&[Card::_As, Card::_Ks, Card::_Qs, Card::_Js, Card::_9d]),
+        ];
+        let mut values = [0; 3];
+        assert_eq!(None, showdown_values(&hands, &mut values));
+        assert_eq!(values[0], values[1]);
+        assert!(values[0] > values[2]);
+        assert!(showdown_values(&hands, &mut [0; 2]).is_some());
+    }
+}
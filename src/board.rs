@@ -0,0 +1,127 @@
+use crate::{nut_hands, wheel_rank_set, BuildingHand, Error, Rank, ShowdownHand, SUIT_MASKS};
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Pairing {
+    Unpaired, Paired, TwoPair, Trips, FullHouse, Quads
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Suitedness {
+    Rainbow,      // no two cards of a suit
+    TwoTone,      // at most two cards of a suit, a flush needs both hole cards
+    ThreeSuited,  // three cards of a suit, not all of them
+    FourSuited,   // four cards of a suit, not all of them
+    Monotone,     // every card the same suit
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BoardTexture {
+    pub pairing: Pairing,
+    pub suitedness: Suitedness,
+    pub connectedness: usize,     // most board ranks inside any one straight
+    pub straights: Vec<Rank>,     // top card of every straight two hole cards can make
+    pub flush_possible: bool,
+    pub nuts: ShowdownHand,       // best hand any two hole cards make right now
+}
+
+/// Texture of a flop, turn or river board.
+pub fn texture(board: &BuildingHand) -> Result<BoardTexture, Error> {
+    if board.len() < 3 || board.len() > 5 {
+        return Err(Error::of(format!("Invalid board, 3 to 5 cards required, {} given", board.len())));
+    }
+
+    let mut counts: Vec<u32> = (0..13)
+        .map(|i| ((board.0 >> (i << 2)) & 0xf).count_ones())
+        .filter(|&count| count > 0)
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let pairing = match counts.as_slice() {
+        [4, ..] => Pairing::Quads,
+        [3, 2, ..] => Pairing::FullHouse,
+        [3, ..] => Pairing::Trips,
+        [2, 2, ..] => Pairing::TwoPair,
+        [2, ..] => Pairing::Paired,
+        _ => Pairing::Unpaired,
+    };
+
+    let most_suited = SUIT_MASKS.iter()
+        .map(|(mask, _)| (board.0 & (mask.clone() as u64)).count_ones() as usize)
+        .max()
+        .unwrap_or(0);
+    let suitedness = match most_suited {
+        n if n == board.len() => Suitedness::Monotone,
+        1 => Suitedness::Rainbow,
+        2 => Suitedness::TwoTone,
+        3 => Suitedness::ThreeSuited,
+        _ => Suitedness::FourSuited,
+    };
+
+    // windows of five ranks, lowest first, the wheel is the window at bit 0
+    let ranks = wheel_rank_set(board.0);
+    let windows: Vec<(u32, usize)> = (0..10)
+        .map(|low| (low + 4, (ranks & (0b11111 << low)).count_ones() as usize))
+        .collect();
+    let connectedness = windows.iter().map(|&(_, count)| count).max().unwrap_or(0);
+    let straights = windows.iter()
+        .rev()
+        .filter(|&&(_, count)| count >= 3)
+        .map(|&(top, _)| Rank::from_bits(top as u8))
+        .collect();
+
+    Ok(BoardTexture {
+        pairing,
+        suitedness,
+        connectedness,
+        straights,
+        flush_possible: most_suited >= 3,
+        nuts: nut_hands(board, &BuildingHand::new(), 1)?.remove(0).0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, Order};
+
+    #[test]
+    fn dry_rainbow_flop() {
+        let board = texture(&hand!("Kd 7c 2h")).unwrap();
+        assert_eq!(Pairing::Unpaired, board.pairing);
+        assert_eq!(Suitedness::Rainbow, board.suitedness);
+        assert_eq!(1, board.connectedness);
+        assert!(board.straights.is_empty());
+        assert!(!board.flush_possible);
+        assert_eq!(Order::Trip, board.nuts.order());
+        assert_eq!([Rank::_K as u8, Rank::_K as u8, Rank::_K as u8, Rank::_7 as u8, Rank::_2 as u8], board.nuts.ranks());
+    }
+
+    #[test]
+    fn wet_monotone_flop() {
+        let board = texture(&hand!("9s 8s 7s")).unwrap();
+        assert_eq!(Suitedness::Monotone, board.suitedness);
+        assert_eq!(3, board.connectedness);
+        assert_eq!(vec![Rank::_J, Rank::_T, Rank::_9], board.straights);
+        assert!(board.flush_possible);
+        assert_eq!(Order::Stfl, board.nuts.order());
+        assert_eq!(Rank::_J as u8, board.nuts.ranks()[0]);
+    }
+
+    #[test]
+    fn paired_two_tone_turn() {
+        let board = texture(&hand!("Kd Kc 7d 4h")).unwrap();
+        assert_eq!(Pairing::Paired, board.pairing);
+        assert_eq!(Suitedness::TwoTone, board.suitedness);
+        assert_eq!(Order::Quad, board.nuts.order());
+    }
+
+    #[test]
+    fn wheel_and_four_flush_river() {
+        let board = texture(&hand!("Ah 2h 3h Th Tc")).unwrap();
+        assert_eq!(Pairing::Paired, board.pairing);
+        assert_eq!(Suitedness::FourSuited, board.suitedness);
+        assert_eq!(vec![Rank::_5], board.straights);
+        assert_eq!(Order::Stfl, board.nuts.order());
+        assert_eq!(Rank::_5 as u8, board.nuts.ranks()[0]);
+        assert!(texture(&hand!("Ah 2h")).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::OnceLock;
+
+use crate::{BuildingHand, ShowdownHand, SUITS};
+
+pub const HAND_CLASSES: usize = 7462;
+
+impl ShowdownHand {
+    /// Class of the five card hand among the 7462 distinct ones, 1 for a royal flush down to 7462
+    /// for 7-5-4-3-2 offsuit, None for hands of fewer cards, five of a kind and short deck hands.
+    pub fn class(&self) -> Option<usize> {
+        classes().binary_search_by(|value| self.value().cmp(value)).ok().map(|index| index + 1)
+    }
+}
+
+// showdown values of every distinct five card hand, best first
+fn classes() -> &'static Vec<u32> {
+    static CLASSES: OnceLock<Vec<u32>> = OnceLock::new();
+    CLASSES.get_or_init(|| {
+        let mut values = Vec::with_capacity(HAND_CLASSES);
+        let mut ranks = [0u64; 5];
+        loop {
+            // suits in turn, never five of one suit and never one card twice
+            let offsuit = ranks.iter()
+                .zip(SUITS.iter().cycle())
+                .fold(0u64, |hand, (rank, suit)| hand | 1 << (rank * 4 + suit.clone() as u64));
+            if offsuit.count_ones() == 5 {
+                values.push(BuildingHand(offsuit).to_showdown_hand().value());
+            }
+            if ranks.windows(2).all(|pair| pair[0] < pair[1]) {
+                let suited = ranks.iter().fold(0u64, |hand, rank| hand | 1 << (rank * 4));
+                values.push(BuildingHand(suited).to_showdown_hand().value());
+            }
+
+            // next ranks in ascending order, each at most 4 times
+            match (0..5).rev().find(|&i| ranks[i] < 12) {
+                Some(i) => {
+                    let rank = ranks[i] + 1;
+                    for r in ranks[i..].iter_mut() {
+                        *r = rank;
+                    }
+                },
+                None => break,
+            }
+        }
+        values.sort_unstable_by(|a, b| b.cmp(a));
+        values.dedup();
+        values
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, Card, Rank};
+
+    #[test]
+    fn every_class() {
+        assert_eq!(HAND_CLASSES, classes().len());
+        assert_eq!(Some(1), hand!("As Ks Qs Js Ts").to_showdown_hand().class());
+        assert_eq!(Some(HAND_CLASSES), hand!("7s 5d 4d 3d 2d").to_showdown_hand().class());
+        assert_eq!(Some(11), hand!("Ad Ac Ah As Kd 2c 3c").to_showdown_hand().class());
+        assert_eq!(None, hand!("As Ks Qs Js").to_showdown_hand().class());
+    }
+
+    #[test]
+    fn same_order_as_benchmark_data() {
+        let data = include_str!("../benchmarks/data/5-card-distinct-ranks.csv");
+        for (i, line) in data.lines().enumerate() {
+            let (ranks, category) = line.split_at(line.find(',').unwrap());
+            let suited = category == ",5" || category == ",8";
+            let mut hand = BuildingHand::new();
+            for (j, rank) in ranks.chars().enumerate() {
+                let suit = if suited { &SUITS[0] } else { &SUITS[j % 4] };
+                let rank = Rank::from_bits(match rank {
+                    'T' => 9, 'J' => 10, 'Q' => 11, 'K' => 12, 'A' => 13,
+                    digit => digit.to_digit(10).unwrap() as u8 - 1,
+                });
+                hand.add_card(&Card::from(rank, suit.clone()), true);
+            }
+            assert_eq!(Some(i + 1), hand.to_showdown_hand().class(), "{}", line);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use rand::Rng;
+
+use crate::{BuildingHand, Card, Error, CARDS};
+
+/* |............|....................................................|
+ * - 12 bits: TBD
+ * - 52 bits: card bit positions of the cards left in the deck, see enum Card
+ */
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Deck(u64);
+
+impl Default for Deck {
+    fn default() -> Deck {
+        Deck::new()
+    }
+}
+
+impl Deck {
+    pub fn new() -> Deck {
+        Deck(0x000fffffffffffff)
+    }
+
+    /// 36 card deck for short deck (6+) hold'em, twos through fives removed.
+    pub fn short() -> Deck {
+        Deck(0x000fffffffff0000)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.0 & (card.clone() as u64) != 0
+    }
+
+    pub fn cards(&self) -> Vec<Card> {
+        CARDS.iter()
+            .filter(|&card| self.contains(card))
+            .cloned()
+            .collect()
+    }
+
+    /// The cards left in the deck, as a hand.
+    pub fn to_building_hand(&self) -> BuildingHand {
+        BuildingHand(self.0)
+    }
+
+    pub fn remove_card(&mut self, card: &Card) -> Option<Error> {
+        if !self.contains(card) {
+            Some(Error::of(format!("Card not in deck: {}", card)))
+        } else {
+            self.0 &= !(card.clone() as u64);
+            None
+        }
+    }
+
+    /// Removes every card of the hand, e.g. hole cards and board already dealt.
+    pub fn remove_hand(&mut self, hand: &BuildingHand) -> Option<Error> {
+        for card in hand.cards() {
+            if let Some(e) = self.remove_card(&card) {
+                return Some(e);
+            }
+        }
+        None
+    }
+
+    pub fn deal<R: Rng>(&mut self, rng: &mut R) -> Option<Card> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let card = self.cards()[rng.gen_range(0, self.len())].clone();
+        self.0 &= !(card.clone() as u64);
+        Some(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rank;
+
+    #[test]
+    fn short_deck_has_six_through_ace() {
+        let deck = Deck::short();
+        assert_eq!(36, deck.len());
+        assert!(deck.cards().iter().all(|card| card.rank() >= Rank::_6));
+        assert!(deck.contains(&Card::_6d));
+        assert!(!deck.contains(&Card::_5s));
+    }
+
+    #[test]
+    fn deal_until_empty() {
+        let mut rng = rand::thread_rng();
+        let mut deck = Deck::short();
+        let mut dealt = BuildingHand::new();
+        while let Some(card) = deck.deal(&mut rng) {
+            assert_eq!(None, dealt.add_card(&card, true));
+        }
+        assert_eq!(Deck::short().to_building_hand(), dealt);
+        assert!(deck.is_empty());
+    }
+
+    #[test]
+    fn remove_card_not_in_deck() {
+        let mut deck = Deck::new();
+        assert_eq!(None, deck.remove_card(&Card::_As));
+        assert!(deck.remove_card(&Card::_As).is_some());
+        assert_eq!(51, deck.len());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{check_disjoint, showdown_values, BuildingHand, Error};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equity {
+    pub wins: u64,   // boards the hand wins alone
+    pub ties: u64,   // boards the hand splits with others
+    pub boards: u64, // every board dealt
+    pub equity: f64, // share of the pot over every board, split pots shared evenly
+}
+
+impl Equity {
+    pub fn win(&self) -> f64 {
+        self.wins as f64 / self.boards as f64
+    }
+
+    pub fn tie(&self) -> f64 {
+        self.ties as f64 / self.boards as f64
+    }
+}
+
+/// Exact all-in equity of each hold'em hand, dealing every board that completes the given one.
+/// Dead cards are out of the deck.
+pub fn equity(hands: &[BuildingHand], board: &BuildingHand, dead: &BuildingHand) -> Result<Vec<Equity>, Error> {
+    if hands.len() < 2 {
+        return Err(Error::of(format!("Invalid hands, at least 2 required, {} given", hands.len())));
+    }
+    if let Some(hand) = hands.iter().find(|hand| hand.len() != 2) {
+        return Err(Error::of(format!("Invalid hole cards, 2 required, {} given", hand.len())));
+    }
+    if board.len() > 5 {
+        return Err(Error::of(format!("Invalid board, at most 5 cards, {} given", board.len())));
+    }
+
+    let mut known = board.0;
+    for cards in hands.iter().map(|hand| hand.0).chain(Some(dead.0)) {
+        check_disjoint(known, cards)?;
+        known |= cards;
+    }
+
+    let unseen = BuildingHand(0x000fffffffffffff & !known);
+    if unseen.len() < 5 - board.len() {
+        return Err(Error::of(format!("Invalid cards, {} unseen for {} board cards to come", unseen.len(), 5 - board.len())));
+    }
+
+    let mut equities = vec![Equity { wins: 0, ties: 0, boards: 0, equity: 0.0 }; hands.len()];
+    let mut cards = vec![0; hands.len()];
+    let mut values = vec![0; hands.len()];
+
+    for runout in unseen.combinations(5 - board.len()) {
+        for (cards, hand) in cards.iter_mut().zip(hands) {
+            *cards = hand.0 | board.0 | runout.0;
+        }
+        showdown_values(&cards, &mut values);
+
+        let best = values.iter().max().cloned().unwrap_or(0);
+        let winners = values.iter().filter(|&&value| value == best).count();
+        for (equity, &value) in equities.iter_mut().zip(&values) {
+            equity.boards += 1;
+            if value != best {
+                continue;
+            }
+            if winners == 1 {
+                equity.wins += 1;
+            } else {
+                equity.ties += 1;
+            }
+            equity.equity += 1.0 / winners as f64;
+        }
+    }
+
+    for equity in equities.iter_mut() {
+        equity.equity /= equity.boards as f64;
+    }
+    Ok(equities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, ErrorKind};
+
+    #[test]
+    fn flop_equities() {
+        let hands = [hand!("Ah Kh"), hand!("Qs Qd")];
+        let equities = equity(&hands, &hand!("Qh Jh 2c"), &BuildingHand::new()).unwrap();
+        assert_eq!(990, equities[0].boards);
+        assert_eq!(equities[0].wins + equities[1].wins + equities[0].ties, 990);
+        assert_eq!(equities[0].ties, equities[1].ties);
+        assert!((equities[0].equity + equities[1].equity - 1.0).abs() < 1e-9);
+
+        // a dead heart takes away a flush out
+        let dead = equity(&hands, &hand!("Qh Jh 2c"), &hand!("3h")).unwrap();
+        assert_eq!(946, dead[0].boards);
+        assert!(dead[0].equity < equities[0].equity);
+    }
+
+    #[test]
+    fn river_splits() {
+        let hands = [hand!("2c 3c"), hand!("2d 3d"), hand!("Ah 4h")];
+        let equities = equity(&hands, &hand!("As Ks Qd Jc Th"), &BuildingHand::new()).unwrap();
+        let shares: Vec<f64> = equities.iter().map(|equity| equity.equity).collect();
+        assert_eq!(vec![1.0 / 3.0; 3], shares);
+        assert_eq!(1.0, equities[0].tie());
+        assert_eq!(0.0, equities[0].win());
+    }
+
+    #[test]
+    fn invalid_hands() {
+        let none = BuildingHand::new();
+        assert!(equity(&[hand!("Ah Kh")], &none, &none).is_err());
+        let duplicate = equity(&[hand!("Ah Kh"), hand!("Ah Qh")], &none, &none).unwrap_err();
+        assert_eq!((ErrorKind::DuplicateCard, "Duplicate card: Ah"), (duplicate.kind, duplicate.msg()));
+        assert!(equity(&[hand!("Ah Kh"), hand!("Qh")], &none, &none).is_err());
+        let duplicate = equity(&[hand!("Ah Kh"), hand!("Qs Qd")], &hand!("Qh"), &hand!("Qh")).unwrap_err();
+        assert_eq!((ErrorKind::DuplicateCard, "Duplicate card: Qh"), (duplicate.kind, duplicate.msg()));
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use crate::{Error, Order};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Frequencies {
+    pub cards: usize,
+    pub counts: Vec<(Order, u64)>, // hands with each best hand class, best first
+}
+
+impl Frequencies {
+    pub fn total(&self) -> u64 {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn count(&self, order: &Order) -> u64 {
+        self.counts.iter().find(|(class, _)| class == order).map(|(_, count)| *count).unwrap_or(0)
+    }
+
+    pub fn probability(&self, order: &Order) -> f64 {
+        self.count(order) as f64 / self.total() as f64
+    }
+}
+
+// hand classes from a standard deck, best first
+const ORDERS: [Order; 9] = [
+    Order::Stfl, Order::Quad, Order::Boat, Order::Flsh, Order::Strt, Order::Trip, Order::Twop, Order::Pair, Order::High,
+];
+
+/// Exact number of hands of n cards from a 52 card deck with each best hand class.
+pub fn frequencies(n: usize) -> Result<Frequencies, Error> {
+    if n > 52 {
+        return Err(Error::of(format!("Invalid number of cards, 0 to 52 required, {} given", n)));
+    }
+    if n == 0 {
+        return Ok(Frequencies { cards: 0, counts: vec![(Order::None, 1)] });
+    }
+
+    // hands with nothing better than each class, i.e. none of the classes above it
+    let at_most: Vec<u64> = (0..ORDERS.len()).map(|i| avoiding(&ORDERS[..i])[n]).collect();
+    let counts = ORDERS.iter()
+        .enumerate()
+        .map(|(i, order)| (order.clone(), at_most[i] - at_most.get(i + 1).cloned().unwrap_or(0)))
+        .collect();
+
+    Ok(Frequencies { cards: n, counts })
+}
+
+/// Fewest cards from a 52 card deck that always hold a hand of the class, e.g. 27 cards always
+/// hold three of a kind (maybe as part of a better hand), None for five of a kind.
+pub fn guaranteed(order: &Order) -> Option<usize> {
+    match order {
+        Order::None => Some(0),
+        Order::High => Some(1),
+        Order::Fivk => None,
+        _ => avoiding(std::slice::from_ref(order)).iter().position(|&count| count == 0),
+    }
+}
+
+/* State of the cards dealt so far, rank by rank from the low ace up to the king, where only what
+ * the avoided hand classes need is tracked, the rest stays 0:
+ * - per suit: holds the ace, cards in a row up to this rank, number of cards
+ * - any suit: holds an ace, ranks in a row up to this rank
+ * - ranks with at least three cards, capped at 2, and with at least two cards, capped at 3
+ */
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+struct State {
+    suits: [(bool, u8, u8); 4], // sorted, suits are interchangeable
+    straight: (bool, u8),
+    trips: u8,
+    pairs: u8,
+}
+
+// number of hands of every size with none of the hand classes
+fn avoiding(orders: &[Order]) -> [u64; 53] {
+    let avoids = |order: Order| orders.contains(&order);
+    let flush = avoids(Order::Flsh);
+    let straight_flush = avoids(Order::Stfl) && !flush; // no flush, no straight flush either
+    let straight = avoids(Order::Strt);
+    let pairing = [Order::Boat, Order::Trip, Order::Twop, Order::Pair].iter().any(|order| orders.contains(order));
+
+    let violates = |state: &State| {
+        (straight_flush && state.suits.iter().any(|&(_, run, _)| run >= 5))
+            || (flush && state.suits.iter().any(|&(_, _, count)| count >= 5))
+            || (straight && state.straight.1 >= 5)
+            || (avoids(Order::Boat) && state.trips >= 1 && state.pairs >= 2)
+            || (avoids(Order::Trip) && state.trips >= 1)
+            || (avoids(Order::Twop) && state.pairs >= 2)
+            || (avoids(Order::Pair) && state.pairs >= 1)
+    };
+
+    let start = State { suits: [(false, 0, 0); 4], straight: (false, 0), trips: 0, pairs: 0 };
+    let mut states: HashMap<State, [u64; 53]> = HashMap::new();
+    let mut counts = [0; 53];
+    counts[0] = 1;
+    states.insert(start, counts);
+
+    // the ace plays low first, then every other rank
+    for rank in 0..13 {
+        let mut next: HashMap<State, [u64; 53]> = HashMap::new();
+        for (state, counts) in &states {
+            for suits in 0..16u8 {
+                let n = suits.count_ones() as usize;
+                if n == 4 && avoids(Order::Quad) {
+                    continue;
+                }
+
+                let mut dealt = state.clone();
+                for (i, suit) in dealt.suits.iter_mut().enumerate() {
+                    let has = suits & (1 << i) != 0;
+                    if rank == 0 && has && straight_flush {
+                        suit.0 = true;
+                    }
+                    if straight_flush {
+                        suit.1 = if has { suit.1 + 1 } else { 0 };
+                    }
+                    if flush && has {
+                        suit.2 += 1;
+                    }
+                }
+                dealt.suits.sort();
+                if straight {
+                    dealt.straight = (dealt.straight.0 || (rank == 0 && n > 0), if n > 0 { dealt.straight.1 + 1 } else { 0 });
+                }
+                if pairing {
+                    dealt.trips = (dealt.trips + (n >= 3) as u8).min(2);
+                    dealt.pairs = (dealt.pairs + (n >= 2) as u8).min(3);
+                }
+                if violates(&dealt) {
+                    continue;
+                }
+
+                let sums = next.entry(dealt).or_insert([0; 53]);
+                for (cards, &count) in counts.iter().enumerate().take(53 - n) {
+                    sums[cards + n] += count;
+                }
+            }
+        }
+        states = next;
+    }
+
+    // the ace plays high last, it completes a straight from the ten
+    let mut avoided = [0; 53];
+    for (state, counts) in &states {
+        if straight_flush && state.suits.iter().any(|&(ace, run, _)| ace && run >= 4) {
+            continue;
+        }
+        if straight && state.straight.0 && state.straight.1 >= 4 {
+            continue;
+        }
+        for (sum, count) in avoided.iter_mut().zip(counts.iter()) {
+            *sum += count;
+        }
+    }
+    avoided
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn five_card_hands() {
+        let frequencies = frequencies(5).unwrap();
+        assert_eq!(2_598_960, frequencies.total());
+        let counts: Vec<u64> = frequencies.counts.iter().map(|(_, count)| *count).collect();
+        assert_eq!(vec![40, 624, 3_744, 5_108, 10_200, 54_912, 123_552, 1_098_240, 1_302_540], counts);
+        assert!((frequencies.probability(&Order::Pair) - 0.422569).abs() < 1e-6);
+    }
+
+    #[test]
+    fn seven_card_hands() {
+        let frequencies = frequencies(7).unwrap();
+        assert_eq!(133_784_560, frequencies.total());
+        let counts: Vec<u64> = frequencies.counts.iter().map(|(_, count)| *count).collect();
+        assert_eq!(vec![41_584, 224_848, 3_473_184, 4_047_644, 6_180_020, 6_461_620, 31_433_400, 58_627_800, 23_294_460], counts);
+    }
+
+    #[test]
+    fn partial_and_whole_deck() {
+        let two = frequencies(2).unwrap();
+        assert_eq!(78, two.count(&Order::Pair));
+        assert_eq!(1_248, two.count(&Order::High));
+        assert_eq!(0, two.count(&Order::Strt));
+
+        assert_eq!(vec![(Order::None, 1)], frequencies(0).unwrap().counts);
+        assert_eq!(1, frequencies(52).unwrap().count(&Order::Stfl));
+        assert_eq!(1, frequencies(52).unwrap().total());
+        assert!(frequencies(53).is_err());
+    }
+
+    #[test]
+    fn guaranteed_hands() {
+        let guaranteed: Vec<Option<usize>> = ORDERS.iter().map(guaranteed).collect();
+        assert_eq!(vec![Some(45), Some(40), Some(27), Some(17), Some(45), Some(27), Some(17), Some(14), Some(1)], guaranteed);
+        assert_eq!(None, super::guaranteed(&Order::Fivk));
+    }
+}
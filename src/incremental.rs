@@ -0,0 +1,151 @@
+use crate::{BuildingHand, Card, Error, ErrorKind, RealizedHand, ShowdownHand, CARDS};
+
+/* Summaries of the hand kept up to date card by card, so evaluating never rescans the 52 bits:
+ * - per suit: set of ranks, bit (rank - 1)
+ * - per count: set of ranks with at least 1, 2, 3 and 4 cards
+ */
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct IncrementalHand {
+    cards: BuildingHand,
+    suits: [u16; 4],
+    counts: [u16; 4],
+}
+
+impl IncrementalHand {
+    pub fn new() -> IncrementalHand {
+        IncrementalHand { cards: BuildingHand::new(), suits: [0; 4], counts: [0; 4] }
+    }
+
+    pub fn add_card(&mut self, card: &Card) -> Option<Error> {
+        if self.cards.contains(card) {
+            return Some(error!("Duplicate card", "{}", card).kind(ErrorKind::DuplicateCard));
+        }
+        self.cards.0 |= card.clone() as u64;
+
+        let (rank, suit) = position(card);
+        self.suits[suit] |= 1 << rank;
+        let count = self.count(rank);
+        self.counts[count] |= 1 << rank;
+        None
+    }
+
+    pub fn remove_card(&mut self, card: &Card) -> Option<Error> {
+        if !self.cards.contains(card) {
+            return Some(error!("Card not in hand", "{}", card));
+        }
+        self.cards.0 &= !(card.clone() as u64);
+
+        let (rank, suit) = position(card);
+        self.suits[suit] &= !(1 << rank);
+        let count = self.count(rank);
+        self.counts[count - 1] &= !(1 << rank);
+        None
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn to_building_hand(&self) -> BuildingHand {
+        self.cards.clone()
+    }
+
+    /// Same as BuildingHand::to_realized_hand, from the summaries.
+    pub fn to_realized_hand(&self) -> RealizedHand {
+        self.cards.classify(&self.suits, &self.counts)
+    }
+
+    pub fn to_showdown_hand(&self) -> ShowdownHand {
+        self.to_realized_hand().to_showdown_hand()
+    }
+
+    // cards of the rank before an add or after a removal
+    fn count(&self, rank: usize) -> usize {
+        self.counts.iter().filter(|&&ranks| ranks & (1 << rank) != 0).count()
+    }
+}
+
+impl BuildingHand {
+    pub fn to_incremental_hand(&self) -> IncrementalHand {
+        let mut hand = IncrementalHand::new();
+        for card in CARDS.iter().filter(|card| self.contains(card)) {
+            hand.add_card(card);
+        }
+        hand
+    }
+}
+
+// rank index from 0 for deuces, suit index
+fn position(card: &Card) -> (usize, usize) {
+    let bit = card.clone() as u64;
+    let index = bit.trailing_zeros() as usize;
+    (index >> 2, index & 0b11)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, Order};
+    use rand::seq::SliceRandom;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn add_and_remove_cards() {
+        let mut flop = hand!("As Ks Qs Jd 2c").to_incremental_hand();
+        assert_eq!(Order::High, flop.to_realized_hand().order());
+        assert!(flop.add_card(&Card::_As).is_some());
+        assert!(flop.remove_card(&Card::_Ts).is_some());
+
+        // runouts reuse the flop state
+        let mut straights = 0;
+        let cards = flop.to_building_hand();
+        for card in CARDS.iter().filter(|card| !cards.contains(card)) {
+            flop.add_card(card);
+            if flop.to_realized_hand().order() >= Order::Strt {
+                straights += 1;
+            }
+            flop.remove_card(card);
+        }
+        assert_eq!(4, straights);
+        assert_eq!(hand!("As Ks Qs Jd 2c").to_incremental_hand(), flop);
+    }
+
+    #[test]
+    fn same_as_building_hand() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut deck = CARDS.to_vec();
+        for n in 0..=12 {
+            for _ in 0..2_000 {
+                deck.shuffle(&mut rng);
+                let mut hand = IncrementalHand::new();
+                for card in &deck[..n] {
+                    hand.add_card(card);
+                }
+                let building = hand.to_building_hand();
+                assert_eq!(building.to_realized_hand(), hand.to_realized_hand(), "{:?}", building);
+                assert_eq!(hand, building.to_incremental_hand());
+            }
+        }
+    }
+
+    #[test]
+    fn every_category() {
+        let cases = [
+            (Order::Stfl, hand!("Ah 2h 3h 4h 5h 6d")),
+            (Order::Quad, hand!("9d 9c 9h 9s Kd Kc Kh")),
+            (Order::Boat, hand!("9d 9c 9h Kd Kc Kh")),
+            (Order::Flsh, hand!("Ah 2h 3h 4h 9h 5d")),
+            (Order::Strt, hand!("Ah 2h 3h 4h 5d")),
+            (Order::Twop, hand!("Ah Ad 3h 3d 5d 5c")),
+        ];
+        for (order, cards) in cases.iter() {
+            let hand = cards.to_incremental_hand();
+            assert_eq!(*order, hand.to_realized_hand().order());
+            assert_eq!(hand.to_building_hand().to_realized_hand(), hand.to_realized_hand());
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+
+use crate::{check_disjoint, spread, suit_ranks, BuildingHand, Error, SUITS};
+
+/* A hand is indexed round by round, e.g. hole cards, flop, turn and river. Per suit, the number
+ * of cards it got in each round makes a tuple, and the 4 tuples sorted make the configuration of
+ * the hand. Every configuration owns a range of indexes, and within it:
+ * - each suit indexes its ranks per round as a combination of the ranks left in that suit
+ * - suits with the same tuple are interchangeable, so they index as a multiset of suit indexes
+ * - the groups of interchangeable suits combine as a mixed radix number
+ */
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HandIndexer {
+    rounds: Vec<usize>,
+    configurations: Vec<Vec<Configuration>>,        // per round, sorted by offset
+    lookup: Vec<HashMap<Vec<Vec<u8>>, usize>>,      // per round, configuration position by tuples
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Configuration {
+    suits: Vec<Vec<u8>>, // cards per round of each suit, sorted in descending order
+    offset: u64,
+    size: u64,
+}
+
+impl HandIndexer {
+    /// Indexer for hands dealt in rounds of the given number of cards.
+    pub fn new(rounds: &[usize]) -> Result<HandIndexer, Error> {
+        if rounds.is_empty() || rounds.contains(&0) || rounds.iter().sum::<usize>() > 52 {
+            return Err(Error::of(format!("Invalid rounds: {:?}", rounds)));
+        }
+
+        let mut configurations = Vec::new();
+        let mut lookup = Vec::new();
+        let mut partial: Vec<Vec<Vec<u8>>> = vec![vec![vec![]; 4]];
+
+        for &cards in rounds {
+            let mut next: Vec<Vec<Vec<u8>>> = partial.iter().flat_map(|suits| deal(suits, cards)).collect();
+            next.sort_by(|a, b| b.cmp(a));
+            next.dedup();
+
+            let mut offset = 0;
+            let round: Vec<Configuration> = next.iter()
+                .map(|suits| {
+                    let size = groups(suits).iter().map(|&(tuple, n)| binomial(suit_size(tuple) + n as u64 - 1, n as u64)).product();
+                    let configuration = Configuration { suits: suits.clone(), offset, size };
+                    offset += size;
+                    configuration
+                })
+                .collect();
+
+            lookup.push(round.iter().enumerate().map(|(i, configuration)| (configuration.suits.clone(), i)).collect());
+            configurations.push(round);
+            partial = next;
+        }
+
+        Ok(HandIndexer { rounds: rounds.to_vec(), configurations, lookup })
+    }
+
+    /// Hold'em hole cards, then the board as one round, 0 board cards for preflop.
+    /// Hands index as (hole, board), e.g. 13,960,050 turn hands, where round by round
+    /// with [2, 3, 1] there are 55,190,538 as the turn card is told apart from the flop.
+    pub fn holdem(board: usize) -> Result<HandIndexer, Error> {
+        match board {
+            0 => HandIndexer::new(&[2]),
+            3..=5 => HandIndexer::new(&[2, board]),
+            _ => Err(Error::of(format!("Invalid board, 0 or 3 to 5 cards required, {} given", board))),
+        }
+    }
+
+    pub fn rounds(&self) -> &[usize] {
+        &self.rounds
+    }
+
+    /// Number of suit isomorphic hands after the given round, 0 for the first.
+    pub fn size(&self, round: usize) -> u64 {
+        self.configurations.get(round)
+            .and_then(|configurations| configurations.last())
+            .map(|last| last.offset + last.size)
+            .unwrap_or(0)
+    }
+
+    /// Index of the cards dealt so far, one hand per round. Hands that only differ by a
+    /// permutation of suits share an index.
+    pub fn index(&self, cards: &[BuildingHand]) -> Result<u64, Error> {
+        if cards.is_empty() || cards.len() > self.rounds.len() {
+            return Err(Error::of(format!("Invalid rounds, 1 to {} required, {} given", self.rounds.len(), cards.len())));
+        }
+        let mut seen = 0;
+        for (hand, &n) in cards.iter().zip(&self.rounds) {
+            if hand.len() != n || hand.jokers() > 0 {
+                return Err(Error::of(format!("Invalid round, {} cards required, {} given", n, hand.len())));
+            }
+            check_disjoint(seen, hand.0)?;
+            seen |= hand.0;
+        }
+
+        let mut suits: Vec<(Vec<u8>, u64)> = SUITS.iter()
+            .map(|suit| {
+                let (mut tuple, mut index, mut radix, mut used) = (vec![], 0, 1, 0);
+                for hand in cards {
+                    let ranks = suit_ranks(hand.0, suit);
+                    tuple.push(ranks.count_ones() as u8);
+                    index += radix * combination_index(ranks, used);
+                    radix *= binomial(13 - used.count_ones() as u64, ranks.count_ones() as u64);
+                    used |= ranks;
+                }
+                (tuple, index)
+            })
+            .collect();
+        suits.sort_by(|a, b| b.cmp(a));
+
+        let round = cards.len() - 1;
+        let key: Vec<Vec<u8>> = suits.iter().map(|(tuple, _)| tuple.clone()).collect();
+        let configuration = &self.configurations[round][self.lookup[round][&key]];
+
+        let (mut index, mut radix, mut i) = (0, 1, 0);
+        for (tuple, n) in groups(&configuration.suits) {
+            let mut indexes: Vec<u64> = suits[i..i + n].iter().map(|(_, index)| *index).collect();
+            indexes.sort_unstable();
+            let multiset: u64 = indexes.iter().enumerate().map(|(k, &index)| binomial(index + k as u64, k as u64 + 1)).sum();
+
+            index += radix * multiset;
+            radix *= binomial(suit_size(tuple) + n as u64 - 1, n as u64);
+            i += n;
+        }
+
+        Ok(configuration.offset + index)
+    }
+
+    /// Canonical cards of an index, one hand per round up to the given round, None if out of range.
+    /// The suit with the most cards is diamonds, then clubs, hearts and spades.
+    pub fn unindex(&self, round: usize, index: u64) -> Option<Vec<BuildingHand>> {
+        if index >= self.size(round) {
+            return None;
+        }
+        let configurations = &self.configurations[round];
+        let configuration = &configurations[configurations.partition_point(|configuration| configuration.offset <= index) - 1];
+
+        let mut hands = vec![BuildingHand::new(); round + 1];
+        let (mut rest, mut i) = (index - configuration.offset, 0);
+
+        for (tuple, n) in groups(&configuration.suits) {
+            let size = suit_size(tuple);
+            let count = binomial(size + n as u64 - 1, n as u64);
+            let mut multiset = rest % count;
+            rest /= count;
+
+            for k in (1..=n as u64).rev() {
+                let top = largest_below(multiset, k, size + n as u64 - 1);
+                multiset -= binomial(top, k);
+                let mut suit_index = top - (k - 1);
+
+                let suit = &SUITS[i + k as usize - 1];
+                let mut used = 0u64;
+                for (hand, &cards) in hands.iter_mut().zip(tuple) {
+                    let left = 13 - used.count_ones() as u64;
+                    let combinations = binomial(left, cards as u64);
+                    let ranks = combination_ranks(suit_index % combinations, cards as u64, used);
+                    suit_index /= combinations;
+                    hand.0 |= spread(ranks, suit);
+                    used |= ranks;
+                }
+            }
+            i += n;
+        }
+
+        Some(hands)
+    }
+}
+
+// every way to deal n more cards to the suits, tuples sorted in descending order
+fn deal(suits: &[Vec<u8>], n: usize) -> Vec<Vec<Vec<u8>>> {
+    let left: Vec<usize> = suits.iter().map(|tuple| 13 - tuple.iter().map(|&cards| cards as usize).sum::<usize>()).collect();
+    let mut dealt = Vec::new();
+    for a in 0..=left[0].min(n) {
+        for b in 0..=left[1].min(n - a) {
+            for c in 0..=left[2].min(n - a - b) {
+                let d = n - a - b - c;
+                if d > left[3] {
+                    continue;
+                }
+                let mut next: Vec<Vec<u8>> = suits.iter()
+                    .zip(&[a, b, c, d])
+                    .map(|(tuple, &cards)| tuple.iter().cloned().chain(Some(cards as u8)).collect())
+                    .collect();
+                next.sort_by(|a, b| b.cmp(a));
+                dealt.push(next);
+            }
+        }
+    }
+    dealt
+}
+
+// runs of interchangeable suits, tuple and number of suits
+fn groups(suits: &[Vec<u8>]) -> Vec<(&[u8], usize)> {
+    let mut groups: Vec<(&[u8], usize)> = Vec::new();
+    for tuple in suits {
+        match groups.last_mut() {
+            Some((last, n)) if *last == tuple.as_slice() => *n += 1,
+            _ => groups.push((tuple, 1)),
+        }
+    }
+    groups
+}
+
+// number of ways to deal one suit its cards per round
+fn suit_size(tuple: &[u8]) -> u64 {
+    let mut used = 0;
+    tuple.iter().fold(1, |size, &cards| {
+        let ways = binomial(13 - used, cards as u64);
+        used += cards as u64;
+        size * ways
+    })
+}
+
+// colex rank of the ranks among those not used yet
+fn combination_index(ranks: u64, used: u64) -> u64 {
+    (0..13).filter(|rank| ranks & (1 << rank) != 0)
+        .enumerate()
+        .map(|(k, rank)| binomial(rank - (used & ((1 << rank) - 1)).count_ones() as u64, k as u64 + 1))
+        .sum()
+}
+
+// inverse of combination_index
+fn combination_ranks(mut index: u64, k: u64, used: u64) -> u64 {
+    let free: Vec<u64> = (0..13).filter(|rank| used & (1 << rank) == 0).collect();
+    let mut ranks = 0;
+    for k in (1..=k).rev() {
+        let position = largest_below(index, k, free.len() as u64);
+        index -= binomial(position, k);
+        ranks |= 1 << free[position as usize];
+    }
+    ranks
+}
+
+// largest n below the limit with binomial(n, k) <= index
+fn largest_below(index: u64, k: u64, limit: u64) -> u64 {
+    let (mut low, mut high) = (k - 1, limit - 1);
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if binomial(mid, k) <= index {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+fn binomial(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1u128, |c, i| c * (n - i) as u128 / (i + 1) as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, preflop_index, ErrorKind};
+
+    #[test]
+    fn holdem_sizes() {
+        let sizes: Vec<u64> = [0, 3, 4, 5].iter()
+            .map(|&board| {
+                let indexer = HandIndexer::holdem(board).unwrap();
+                indexer.size(indexer.rounds().len() - 1)
+            })
+            .collect();
+        assert_eq!(vec![169, 1_286_792, 13_960_050, 123_156_254], sizes);
+
+        let rounds = HandIndexer::new(&[2, 3, 1, 1]).unwrap();
+        assert_eq!(1_286_792, rounds.size(1));
+        assert_eq!(55_190_538, rounds.size(2));
+        assert_eq!(0, rounds.size(4));
+        assert_eq!(1755, HandIndexer::new(&[3]).unwrap().size(0));
+        assert!(HandIndexer::new(&[2, 0]).is_err());
+        assert!(HandIndexer::holdem(2).is_err());
+    }
+
+    #[test]
+    fn preflop_is_a_bijection() {
+        let indexer = HandIndexer::holdem(0).unwrap();
+        let mut seen = [false; 169];
+        for hole in BuildingHand(0x000fffffffffffff).combinations(2) {
+            let index = indexer.index(std::slice::from_ref(&hole)).unwrap();
+            let canonical = indexer.unindex(0, index).unwrap();
+            assert_eq!(preflop_index(&hole), preflop_index(&canonical[0]));
+            seen[index as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        assert_eq!(None, indexer.unindex(0, 169));
+    }
+
+    #[test]
+    fn unindex_round_trips() {
+        for indexer in [HandIndexer::new(&[2, 3, 1, 1]).unwrap(), HandIndexer::holdem(5).unwrap()].iter() {
+            for round in 0..indexer.rounds().len() {
+                let size = indexer.size(round);
+                for index in (0..size).step_by(size as usize / 997 + 1).chain(Some(size - 1)) {
+                    let cards = indexer.unindex(round, index).unwrap();
+                    assert_eq!(round + 1, cards.len());
+                    assert_eq!(index, indexer.index(&cards).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn suit_isomorphic_hands_share_an_index() {
+        let indexer = HandIndexer::new(&[2, 3, 1]).unwrap();
+        let a = indexer.index(&[hand!("As Ks"), hand!("Qs 7h 2h"), hand!("2c")]).unwrap();
+        let b = indexer.index(&[hand!("Ad Kd"), hand!("Qd 7c 2c"), hand!("2h")]).unwrap();
+        assert_eq!(a, b);
+
+        // same cards, but the turn came on the flop
+        let c = indexer.index(&[hand!("As Ks"), hand!("Qs 7h 2c"), hand!("2h")]).unwrap();
+        assert_ne!(a, c);
+
+        // a single board round doesn't tell them apart
+        let turn = HandIndexer::holdem(4).unwrap();
+        let a = turn.index(&[hand!("As Ks"), hand!("Qs 7h 2h 2c")]).unwrap();
+        let b = turn.index(&[hand!("Ah Kh"), hand!("Qh 7s 2s 2c")]).unwrap();
+        assert_eq!(a, b);
+
+        assert_eq!(ErrorKind::DuplicateCard, indexer.index(&[hand!("As Ks"), hand!("As 7h 2h")]).unwrap_err().kind);
+        assert!(indexer.index(&[hand!("As")]).is_err());
+        assert!(indexer.index(&[]).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::OnceLock;
+
+use crate::{spread, suit_ranks, BuildingHand, Error, Suit, SUITS};
+
+pub const PREFLOP_CLASSES: usize = 169;
+pub const FLOP_CLASSES: usize = 1755;
+
+/// Canonical suit of every original suit, indexed by suit.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SuitPermutation([Suit; 4]);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Canonical {
+    pub hole: BuildingHand,
+    pub board: BuildingHand,
+    pub permutation: SuitPermutation, // takes the original cards to the canonical ones
+}
+
+impl SuitPermutation {
+    pub fn identity() -> SuitPermutation {
+        SuitPermutation(SUITS.clone())
+    }
+
+    pub fn suit(&self, suit: &Suit) -> Suit {
+        self.0[suit.clone() as usize].clone()
+    }
+
+    /// Same hand with every card moved to its permuted suit, jokers stay.
+    pub fn apply(&self, hand: &BuildingHand) -> BuildingHand {
+        let cards = SUITS.iter()
+            .map(|suit| spread(suit_ranks(hand.0, suit), &self.suit(suit)))
+            .fold(0, |cards, suit_cards| cards | suit_cards);
+        BuildingHand((hand.0 & !0x000fffffffffffff) | cards)
+    }
+
+    pub fn inverse(&self) -> SuitPermutation {
+        let mut inverse = SuitPermutation::identity();
+        for suit in SUITS.iter() {
+            inverse.0[self.suit(suit) as usize] = suit.clone();
+        }
+        inverse
+    }
+}
+
+/// Suit isomorphic hole cards and board, suits renamed so every hand that only differs by
+/// a permutation of suits ends up with the same cards.
+///
+/// Suits are ordered by their hole card ranks first, then by their board ranks, each compared as
+/// a rank bitmask, so the suit with the highest card leads whatever the card counts, and the first
+/// suit becomes diamonds, then clubs, hearts and spades.
+pub fn canonicalize(hole: &BuildingHand, board: &BuildingHand) -> Canonical {
+    let mut suits = SUITS.clone();
+    suits.sort_by(|a, b| {
+        let key = |suit: &Suit| (suit_ranks(hole.0, suit), suit_ranks(board.0, suit));
+        key(b).cmp(&key(a))
+    });
+
+    let mut permutation = SuitPermutation::identity();
+    for (suit, canonical) in suits.iter().zip(SUITS.iter()) {
+        permutation.0[suit.clone() as usize] = canonical.clone();
+    }
+
+    Canonical { hole: permutation.apply(hole), board: permutation.apply(board), permutation }
+}
+
+/// Index of the starting hand class, pairs, suited and offsuit hands ordered AA, AKs, AKo, AQs, ... 22.
+pub fn preflop_index(hole: &BuildingHand) -> Result<usize, Error> {
+    if hole.len() != 2 {
+        return Err(Error::of(format!("Invalid hole cards, 2 required, {} given", hole.len())));
+    }
+    let canonical = canonicalize(hole, &BuildingHand::new()).hole;
+    Ok(preflop_hands().position(|hand| hand == canonical).expect("Bug! Unknown starting hand class"))
+}
+
+/// Canonical hole cards of a starting hand class, None past the last class.
+pub fn preflop_hand(index: usize) -> Option<BuildingHand> {
+    preflop_hands().nth(index)
+}
+
+/// Index of the suit isomorphic flop class.
+pub fn flop_index(board: &BuildingHand) -> Result<usize, Error> {
+    if board.len() != 3 {
+        return Err(Error::of(format!("Invalid flop, 3 cards required, {} given", board.len())));
+    }
+    let canonical = canonicalize(&BuildingHand::new(), board).board;
+    Ok(flops().binary_search(&canonical.0).expect("Bug! Unknown flop class"))
+}
+
+/// Canonical cards of a flop class, None past the last class.
+pub fn flop_board(index: usize) -> Option<BuildingHand> {
+    flops().get(index).map(|&cards| BuildingHand(cards))
+}
+
+// canonical starting hands in index order, the first suits are the canonical ones
+fn preflop_hands() -> impl Iterator<Item = BuildingHand> {
+    (0..13u64).rev().flat_map(|high| (0..=high).rev().flat_map(move |low| {
+        let (high, low) = (high << 2, low << 2);
+        if high == low {
+            vec![BuildingHand((0b11) << high)]
+        } else {
+            vec![BuildingHand((1 << high) | (1 << low)), BuildingHand((1 << high) | (0b10 << low))]
+        }
+    }))
+}
+
+// canonical flops in ascending order of their card bits
+fn flops() -> &'static Vec<u64> {
+    static FLOPS: OnceLock<Vec<u64>> = OnceLock::new();
+    FLOPS.get_or_init(|| {
+        let mut flops: Vec<u64> = BuildingHand(0x000fffffffffffff).combinations(3)
+            .iter()
+            .map(|board| canonicalize(&BuildingHand::new(), board).board.0)
+            .collect();
+        flops.sort_unstable();
+        flops.dedup();
+        flops
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand;
+
+    #[test]
+    fn canonical_suits() {
+        let a = canonicalize(&hand!("As Ks"), &hand!("Qh 7s 2c"));
+        let b = canonicalize(&hand!("Ac Kc"), &hand!("Qs 7c 2d"));
+        assert_eq!(a.hole, b.hole);
+        assert_eq!(a.board, b.board);
+        assert_eq!(hand!("Ad Kd"), a.hole);
+        assert_eq!(hand!("Qc 7d 2h"), a.board);
+
+        // the permutation and its inverse take the cards back and forth
+        assert_eq!(hand!("As Ks"), a.permutation.inverse().apply(&a.hole));
+        assert_eq!(Suit::_D, a.permutation.suit(&Suit::_S));
+        assert_eq!(Suit::_S, a.permutation.inverse().suit(&Suit::_D));
+    }
+
+    #[test]
+    fn preflop_classes() {
+        assert_eq!(PREFLOP_CLASSES, preflop_hands().count());
+        assert_eq!(None, preflop_hand(PREFLOP_CLASSES));
+
+        assert_eq!(0, preflop_index(&hand!("Ah As")).unwrap());
+        assert_eq!(1, preflop_index(&hand!("Kh Ah")).unwrap());
+        assert_eq!(2, preflop_index(&hand!("Kc Ah")).unwrap());
+        assert_eq!(PREFLOP_CLASSES - 1, preflop_index(&hand!("2c 2h")).unwrap());
+
+        for index in 0..PREFLOP_CLASSES {
+            assert_eq!(index, preflop_index(&preflop_hand(index).unwrap()).unwrap());
+        }
+
+        // 6 pairs, 4 suited and 12 offsuit combinations of each class
+        let mut counts = vec![0; PREFLOP_CLASSES];
+        for hole in BuildingHand(0x000fffffffffffff).combinations(2) {
+            counts[preflop_index(&hole).unwrap()] += 1;
+        }
+        assert_eq!([6, 4, 12], counts[..3]);
+        assert_eq!(1326, counts.iter().sum::<usize>());
+        assert!(preflop_index(&hand!("2c")).is_err());
+    }
+
+    #[test]
+    fn flop_classes() {
+        assert_eq!(FLOP_CLASSES, flops().len());
+        assert_eq!(None, flop_board(FLOP_CLASSES));
+
+        for index in 0..FLOP_CLASSES {
+            assert_eq!(index, flop_index(&flop_board(index).unwrap()).unwrap());
+        }
+
+        let a = flop_index(&hand!("9s 8s 2h")).unwrap();
+        let b = flop_index(&hand!("2c 9d 8d")).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, flop_index(&hand!("9s 8h 2h")).unwrap());
+        assert!(flop_index(&hand!("2c 9d")).is_err());
+    }
+}
@@ -1,8 +1,129 @@
-use std::cmp::Ordering;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "std")]
 use std::error;
-use std::fmt;
+
+// Error with its details formatted after a colon, or just the fixed message without std
+#[cfg(feature = "std")]
+macro_rules! error {
+    ($msg:literal) => {
+        $crate::Error::of($msg.to_owned())
+    };
+    ($msg:literal, $($details:tt)+) => {
+        $crate::Error::of(format!(concat!($msg, ": {}"), format_args!($($details)+)))
+    };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! error {
+    ($msg:literal $(, $($details:tt)+)?) => {
+        $crate::Error::of($msg)
+    };
+}
+
+#[cfg(feature = "std")]
+mod analysis;
+#[cfg(feature = "std")]
+mod badugi;
+mod batch;
+#[cfg(feature = "std")]
+mod class;
+#[cfg(feature = "std")]
+mod board;
+#[cfg(feature = "std")]
+mod deck;
+#[cfg(feature = "std")]
+mod equity;
+#[cfg(feature = "std")]
+mod frequency;
+mod incremental;
+#[cfg(feature = "std")]
+mod indexer;
+#[cfg(feature = "std")]
+mod isomorphism;
+#[cfg(feature = "std")]
+mod lowball;
+#[cfg(feature = "std")]
+mod nuts;
+#[cfg(feature = "std")]
+mod outs;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "std")]
+mod strength;
+#[cfg(feature = "std")]
+mod stud;
+#[cfg(feature = "std")]
+mod variant;
+#[cfg(feature = "std")]
+mod wild;
+
+#[cfg(feature = "std")]
+pub use analysis::{analyze, Analysis, Draw, MadeHand};
+#[cfg(feature = "std")]
+pub use badugi::BadugiHand;
+pub use batch::{showdown_hands, showdown_values};
+#[cfg(feature = "std")]
+pub use class::HAND_CLASSES;
+#[cfg(feature = "std")]
+pub use board::{texture, BoardTexture, Pairing, Suitedness};
+#[cfg(feature = "std")]
+pub use deck::Deck;
+#[cfg(feature = "std")]
+pub use equity::{equity, Equity};
+#[cfg(feature = "std")]
+pub use frequency::{frequencies, guaranteed, Frequencies};
+pub use incremental::IncrementalHand;
+#[cfg(feature = "std")]
+pub use indexer::HandIndexer;
+#[cfg(feature = "std")]
+pub use isomorphism::{canonicalize, flop_board, flop_index, preflop_hand, preflop_index, Canonical, SuitPermutation, FLOP_CLASSES, PREFLOP_CLASSES};
+#[cfg(feature = "std")]
+pub use lowball::{LowHand, Lowball};
+#[cfg(feature = "std")]
+pub use nuts::{nut_hands, nut_ranking, NutRanking};
+#[cfg(feature = "std")]
+pub use outs::{outs, Outs};
+#[cfg(feature = "std")]
+pub use strength::{hand_strength, HandStrength};
+#[cfg(feature = "std")]
+pub use stud::{bring_in, first_to_act, StudGame, StudHand};
+#[cfg(feature = "std")]
+pub use variant::{ShortDeckRules, Variant};
+#[cfg(feature = "std")]
+pub use wild::WildRules;
+
+/// BuildingHand of cards like "As Ks Qs Js Ts" built at compile time, for hands and boards in
+/// test tables and constants.
+///
+/// ```
+/// const BOARD: poker::BuildingHand = poker::hand!("Qs Js Ts");
+/// assert_eq!(3, BOARD.len());
+/// assert_eq!(poker::Order::Stfl, poker::hand!("AsKsQsJsTs").to_realized_hand().order());
+/// ```
+///
+/// Invalid and duplicate cards don't compile:
+///
+/// ```compile_fail
+/// let hand = poker::hand!("As Ks Xs");
+/// ```
+///
+/// ```compile_fail
+/// let hand = poker::hand!("As Ks As");
+/// ```
+#[macro_export]
+macro_rules! hand {
+    ($cards:expr) => {{
+        const HAND: $crate::BuildingHand = $crate::BuildingHand::parse($cards);
+        HAND
+    }};
+}
 
 // TODO: play nice with UTF-8?
+#[cfg(feature = "std")]
 pub fn normalize_input_cards(cards: &str, n_expected: usize) -> Result<Option<Vec<String>>, Error> {
 
     let cards = cards.trim();
@@ -59,14 +180,51 @@ pub fn normalize_input_cards(cards: &str, n_expected: usize) -> Result<Option<Ve
     }
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ErrorKind {
+    InvalidCard,   // input that isn't a card
+    DuplicateCard, // a card given twice
+    InvalidInput,  // anything else the input can't be
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Error {
-    pub msg: String,
+    pub kind: ErrorKind,
+    #[cfg(feature = "std")]
+    msg: String,
+    #[cfg(not(feature = "std"))]
+    msg: &'static str,
+    pub position: Option<usize>, // byte offset of the error in the parsed input
 }
 
 impl Error {
+    #[cfg(feature = "std")]
     fn of(msg: String) -> Error {
-        Error { msg }
+        Error { kind: ErrorKind::InvalidInput, msg, position: None }
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn of(msg: &'static str) -> Error {
+        Error { kind: ErrorKind::InvalidInput, msg, position: None }
+    }
+
+    /// What went wrong, without the details where the crate is built without std.
+    #[cfg(feature = "std")]
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn msg(&self) -> &str {
+        self.msg
+    }
+
+    fn kind(self, kind: ErrorKind) -> Error {
+        Error { kind, ..self }
+    }
+
+    fn at(self, position: usize) -> Error {
+        Error { position: Some(position), ..self }
     }
 }
 
@@ -76,14 +234,16 @@ impl fmt::Display for Error {
     }
 }
 
+#[cfg(feature = "std")]
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         None
     }
 }
 
-/* |............|....................................................|
- * - 12 bits: TBD (8 bits: number of cards in hand?)
+/* |........|....|....................................................|
+ * -  8 bits: TBD (number of cards in hand?)
+ * -  4 bits: number of jokers, which live outside the 52 card bit positions
  * - 52 bits: card bit positions, see enum Card below
  */
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -94,28 +254,81 @@ pub struct BuildingHand(u64);
  * -  4 bits: top card rank, e.g. top card rank of made hand, rank of pair/trip/quad, rank of boat
  * -  4 bits: 2nd card rank, e.g. 2nd card rank of made hand, 2nd of two pairs, filler of boat
  * - 52 bits: card bit positions, see enum Card below
+ * Ranks are 0 where a partial hand (fewer than 5 cards) has no such card.
  */
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct RealizedHand(u64);
 
-/* |....|..|......|....|....|....|....|....|
+/* |....|..|..|....|....|....|....|....|....|
  * -   4 bits: made hand classifier, see enum Order below
  * -   2 bits: suit for flush hands, see enum Suit below
- * -   6 bits: TBD
+ * -   2 bits: ranking of the hand classes, 0 standard, 1 short deck, 2 short deck with trips
+ *             above straights, see ranked_order
+ * -   4 bits: TBD
  * - 5x4 bits: card ranks in descending order from msb to lsb
  */
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct ShowdownHand(u32);
 
+impl Default for BuildingHand {
+    fn default() -> BuildingHand {
+        BuildingHand::new()
+    }
+}
+
 impl BuildingHand {
-    pub fn new() -> BuildingHand {
+    pub const fn new() -> BuildingHand {
         BuildingHand(0)
     }
 
+    /// Same hand with one more card, for hands built in constants, see also hand!.
+    ///
+    /// Panics on a duplicate card, which is a compile error where the hand is a constant.
+    pub const fn with_card(self, card: Card) -> BuildingHand {
+        let bit = card as u64;
+        if self.0 & bit != 0 {
+            panic!("Duplicate card");
+        }
+        BuildingHand(self.0 | bit)
+    }
+
+    /// Hand of cards like "As Ks Qs Js Ts", spaces optional, see also hand!.
+    ///
+    /// Panics on an invalid or duplicate card, which is a compile error where the hand is a constant.
+    pub const fn parse(cards: &str) -> BuildingHand {
+        let cards = cards.as_bytes();
+        let mut hand = BuildingHand::new();
+        let mut i = 0;
+        while i < cards.len() {
+            if cards[i] == b' ' {
+                i += 1;
+                continue;
+            }
+            if i + 1 == cards.len() {
+                panic!("Invalid cards, rank without suit");
+            }
+            hand = match Card::parse(cards[i], cards[i + 1]) {
+                Some(card) => hand.with_card(card),
+                None => panic!("Invalid card"),
+            };
+            i += 2;
+        }
+        hand
+    }
+
+    /// Hand of raw bits laid out as above, anything past the card and joker bits is dropped.
+    pub const fn from_bits(bits: u64) -> BuildingHand {
+        BuildingHand(bits & 0x00ffffffffffffff)
+    }
+
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
     pub fn add_card(&mut self, card: &Card, err_on_duplicate: bool) -> Option<Error> {
         let bit = card.clone() as u64;
         if err_on_duplicate && self.0 & bit != 0 {
-            Some(Error::of(format!("Duplicate card: {}", card.clone())))
+            Some(error!("Duplicate card", "{}", card).kind(ErrorKind::DuplicateCard))
         } else {
             self.0 |= bit;
             None
@@ -123,196 +336,441 @@ impl BuildingHand {
         }
     }
 
-    fn to_realized_hand(&self) -> RealizedHand {
-        if self.0 & 0x000fffffffffffff == 0 {
-            return self.new_realized_hand(Order::None, 0, 0);
+    pub fn add_joker(&mut self) -> Option<Error> {
+        if self.jokers() == 15 {
+            Some(error!("Too many jokers, at most 15"))
+        } else {
+            self.0 += 1 << 52;
+            None
         }
+    }
 
-        let mask_base = 0x000f000000000000;
-        let mut straight_flush_counter = 0u8;
-        let mut straight_flush_buffer = 0b1111u8;
-        let mut straight_counter = 0u8;
-        let mut best_straight = Option::<u64>::None;
-        let mut best_flush = Option::<u64>::None;
-        let mut best_quads = Option::<u64>::None;
-        let mut best_trips = Option::<u64>::None;
-        let mut best_pair1 = Option::<u64>::None;
-        let mut best_pair2 = Option::<u64>::None;
-        let mut best_ranks = [0u64; 5];
-        let mut best_ranks_idx = 0usize;
-
-        // extra index i=13 for wheel evaluation
-        for i in 0u8..14u8 {
-
-            let mask_shift = i % 13 << 2;
-            let mask_comp = 48 - mask_shift;
-            let mask = mask_base >> mask_shift;
-            let rank = 1 + (mask_comp >> 2) as u64 % 13;
-            let quartet = ((self.0 & mask) >> mask_comp) as u8;
-
-            straight_flush_buffer &= quartet;
-            if straight_flush_buffer > 0 {
-                straight_flush_counter += 1;
-                if straight_flush_counter == 5 {
-                    let top_rank = 1 + (rank + 3) % 13;
-                    return self.new_realized_hand(Order::Stfl, top_rank, top_rank - 1);
-                }
-            } else {
-                straight_flush_counter = 0;
-                straight_flush_buffer = 0b1111;
-            }
-
-            if quartet != 0 {
-                if best_ranks_idx < 5 {
-                    best_ranks[best_ranks_idx] = rank;
-                    best_ranks_idx += 1;
-                }
+    pub fn jokers(&self) -> usize {
+        ((self.0 >> 52) & 0xf) as usize
+    }
 
-                straight_counter += 1;
-                if let (5, None) = (straight_counter, best_straight) {
-                    let top_rank = 1 + (rank + 3) % 13;
-                    best_straight = Some(top_rank)
-                }
-            } else {
-                straight_counter = 0;
-            }
+    /// Number of cards in the hand, not counting jokers.
+    pub fn len(&self) -> usize {
+        (self.0 & 0x000fffffffffffff).count_ones() as usize
+    }
 
-            // wheel evaluation no longer needed at this point
-            if i == 13 {
-                continue;
-            }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-            match quartet {
-                0b1111 => match best_quads {
-                    None => best_quads = Some(rank),
-                    Some(_) => match best_trips {
-                        None => best_trips = Some(rank),
-                        Some(_) => match best_pair1 {
-                            None => best_pair1 = Some(rank),
-                            Some(_) => if let None = best_pair2 {
-                                best_pair2 = Some(rank);
-                            },
-                        },
-                    },
-                },
+    pub fn contains(&self, card: &Card) -> bool {
+        self.0 & (card.clone() as u64) != 0
+    }
 
-                0b1110 | 0b1101 | 0b1011 | 0b0111 => match best_trips {
-                    None => best_trips = Some(rank),
-                    Some(_) => match best_pair1 {
-                        None => best_pair1 = Some(rank),
-                        Some(_) => if let None = best_pair2 {
-                            best_pair2 = Some(rank);
-                        },
-                    },
-                },
+    #[cfg(feature = "std")]
+    pub fn cards(&self) -> Vec<Card> {
+        CARDS.iter()
+            .filter(|&card| self.contains(card))
+            .cloned()
+            .collect()
+    }
 
-                0b1100 | 0b1010 | 0b1001 | 0b0110 | 0b0101 | 0b0011 => match best_pair1 {
-                    None => best_pair1 = Some(rank),
-                    Some(_) => if let None = best_pair2 {
-                        best_pair2 = Some(rank);
-                    },
-                },
-                _ => {},
-            }
+    /// Every sub-hand of exactly `k` cards, in no particular order.
+    #[cfg(feature = "std")]
+    pub fn combinations(&self, k: usize) -> Vec<BuildingHand> {
+        let bits: Vec<u64> = self.cards().into_iter().map(|card| card as u64).collect();
+        let mut combinations = Vec::new();
+        if k > bits.len() {
+            return combinations;
         }
 
-        if let Some(rank1) = best_quads {
-            return self.new_realized_hand(Order::Quad, rank1, self.find_rank2(&best_ranks, rank1));
-        }
+        let mut indices: Vec<usize> = (0..k).collect();
+        loop {
+            combinations.push(BuildingHand(indices.iter().fold(0, |hand, &i| hand | bits[i])));
 
-        if let (Some(rank1), Some(rank2)) = (best_trips, best_pair1) {
-            return self.new_realized_hand(Order::Boat, rank1, rank2);
+            // advance the rightmost index that still has room to move
+            let mut i = k;
+            while i > 0 && indices[i - 1] == bits.len() - k + i - 1 {
+                i -= 1;
+            }
+            if i == 0 {
+                return combinations;
+            }
+            indices[i - 1] += 1;
+            for j in i..k {
+                indices[j] = indices[j - 1] + 1;
+            }
         }
+    }
 
-        // flush algorithm, based on Kernighan bit counting
-        for (mask, offset) in &SUIT_MASKS {
-
-            // normalize bit positions for easy ordering comparison
-            let suited_group = (self.0 & (mask.clone() as u64)) >> offset;
-            let mut suited_cards = suited_group;
+    /// Classifies the best five card hand out of any number of cards.
+    ///
+    /// Hands of one to four cards are partial hands: only pairs, two pair, trips and quads can be
+    /// made, never straights or flushes, and any kicker the hand is too small to have is rank 0.
+    /// An empty hand is Order::None.
+    pub fn to_realized_hand(&self) -> RealizedHand {
+        let suits = suit_masks(self.0);
+        self.classify(&suits, &rank_counts(&suits))
+    }
 
-            // take away 3 cards, ensure flush would have at least 2 left
-            for _ in 0u8..3u8 {
-                if suited_cards == 0 {
-                    break; // avoid underflow
-                }
-                suited_cards = suited_cards & (suited_cards - 1);
-            }
+    // best hand from the ranks of every suit and the ranks with at least 1, 2, 3 and 4 cards
+    fn classify(&self, suits: &[u16; 4], counts: &[u16; 4]) -> RealizedHand {
+        let new = |order: Order, rank1: u64, rank2: u64| self.new_realized_hand(order, rank1, rank2);
+        let top = |ranks: u16| msb(ranks as u64).map(|bit| bit as u64 + 1).unwrap_or(0);
+        let without = |ranks: u16, rank: u64| if rank == 0 { ranks } else { ranks & !(1 << (rank - 1)) };
+        let [ranks, pairs, trips, quads] = *counts;
 
-            // if at most 4 cards of one suit, skip to next suit
-            if suited_cards == 0 || suited_cards & (suited_cards - 1) == 0 {
-                continue;
-            }
+        if ranks == 0 {
+            return new(Order::None, 0, 0);
+        }
 
-            best_flush = Some(match best_flush {
-                None => suited_group,
-                Some(other) => if suited_group > other { suited_group } else { other },
-            });
+        if let Some(rank1) = suits.iter().filter_map(|&suited| straight_top(suited)).max() {
+            return new(Order::Stfl, rank1, rank1 - 1);
         }
 
-        if let Some(suited_group) = best_flush {
-            let ms_bit = msb(suited_group).unwrap();
-            let suited_group = suited_group & !(1u64 << ms_bit);
-            let rank1 = 1 + (ms_bit >> 2) as u64;
-            let rank2 = 1 + (msb(suited_group).unwrap() >> 2) as u64;
-            return self.new_realized_hand(Order::Flsh, rank1, rank2);
+        if quads != 0 {
+            let rank1 = top(quads);
+            return new(Order::Quad, rank1, top(without(ranks, rank1)));
         }
 
-        if let Some(rank1) = best_straight {
-            return self.new_realized_hand(Order::Strt, rank1, rank1 - 1);
+        if trips != 0 && without(pairs, top(trips)) != 0 {
+            let rank1 = top(trips);
+            return new(Order::Boat, rank1, top(without(pairs, rank1)));
         }
 
-        if let Some(rank1) = best_trips {
-            return self.new_realized_hand(Order::Trip, rank1, self.find_rank2(&best_ranks, rank1));
+        if let Some(&suited) = suits.iter().filter(|suited| suited.count_ones() >= 5).max() {
+            let rank1 = top(suited);
+            return new(Order::Flsh, rank1, top(without(suited, rank1)));
         }
 
-        if let Some(rank1) = best_pair1 {
-            return match best_pair2 {
-                Some(rank2) => self.new_realized_hand(Order::Twop, rank1, rank2),
-                None => self.new_realized_hand(Order::Pair, rank1, self.find_rank2(&best_ranks, rank1)),
-            }
+        if let Some(rank1) = straight_top(ranks) {
+            return new(Order::Strt, rank1, rank1 - 1);
         }
 
-        let rank1 = self.find_rank2(&best_ranks, 0);
-        return self.new_realized_hand(Order::High, rank1, self.find_rank2(&best_ranks, rank1));
+        let (order, rank1) = match (trips, pairs) {
+            (0, 0) => (Order::High, top(ranks)),
+            (0, _) if without(pairs, top(pairs)) != 0 => {
+                let rank1 = top(pairs);
+                return new(Order::Twop, rank1, top(without(pairs, rank1)));
+            },
+            (0, _) => (Order::Pair, top(pairs)),
+            _ => (Order::Trip, top(trips)),
+        };
+        new(order, rank1, top(without(ranks, rank1)))
     }
 
     fn new_realized_hand(&self, order: Order, rank1: u64, rank2: u64) -> RealizedHand {
         RealizedHand((order as u64) << 60 | rank1 << 56 | rank2 << 52 | (self.0 & 0x000fffffffffffff))
     }
 
-    fn find_rank2(&self, best_ranks: &[u64; 5], rank1: u64) -> u64 {
-        for &rank2 in best_ranks {
-            if rank2 != rank1 {
-                return rank2;
-            }
-        }
-        0
+    /// Showdown value of the best five cards, see to_realized_hand for hands of fewer cards.
+    pub fn to_showdown_hand(&self) -> ShowdownHand {
+        self.to_realized_hand().to_showdown_hand()
     }
 
-    fn to_showdown_hand(&self) -> ShowdownHand {
-        self.to_realized_hand().to_showdown_hand()
+    /// The five cards of the best hand, any of them when several tie, or all the cards of hands
+    /// of fewer than five. Jokers are left out.
+    #[cfg(feature = "std")]
+    pub fn best_five(&self) -> BuildingHand {
+        let cards = BuildingHand(self.0 & 0x000fffffffffffff);
+        let best = cards.to_showdown_hand();
+        cards.combinations(5).into_iter()
+            .find(|hand| hand.to_showdown_hand() == best)
+            .unwrap_or(cards)
     }
 }
 
 impl RealizedHand {
-    fn to_building_hand(&self) -> BuildingHand {
+    pub fn order(&self) -> Order {
+        Order::from_bits((self.0 >> 60) as u8)
+    }
+
+    pub fn to_building_hand(&self) -> BuildingHand {
         BuildingHand(self.0 & 0x000fffffffffffff)
     }
 
-    fn to_showdown_hand(&self) -> ShowdownHand {
-        ShowdownHand(0) // TODO
+    pub fn to_showdown_hand(&self) -> ShowdownHand {
+        let order = self.order();
+        let rank1 = ((self.0 >> 56) & 0xf) as u32;
+        let rank2 = ((self.0 >> 52) & 0xf) as u32;
+        let cards = self.0 & 0x000fffffffffffff;
+        let ranks = rank_set(cards);
+
+        let without = |ranks: u32, rank: u32| ranks & !(1 << rank >> 1);
+
+        let (suit, ranks) = match order {
+            Order::None => (0, [0; 5]),
+            Order::High => (0, top_ranks(&[], ranks)),
+            Order::Pair => (0, top_ranks(&[rank1; 2], without(ranks, rank1))),
+            Order::Twop => (0, top_ranks(&[rank1, rank1, rank2, rank2], without(without(ranks, rank1), rank2))),
+            Order::Trip => (0, top_ranks(&[rank1; 3], without(ranks, rank1))),
+            Order::Strt => (0, straight_ranks(rank1)),
+            Order::Flsh => {
+                let (suit, suited_ranks) = SUIT_MASKS.iter()
+                    .map(|(mask, offset)| (*offset as u32, rank_set(cards & (mask.clone() as u64))))
+                    .filter(|(_, suited_ranks)| suited_ranks.count_ones() >= 5)
+                    .map(|(suit, suited_ranks)| (suit, top_ranks(&[], suited_ranks)))
+                    .max_by(|(_, a), (_, b)| a.cmp(b))
+                    .expect("Bug! Flush without five suited cards");
+                (suit, suited_ranks)
+            },
+            Order::Boat => (0, [rank1, rank1, rank1, rank2, rank2]),
+            Order::Quad => (0, [rank1, rank1, rank1, rank1, rank2]),
+            Order::Fivk => (0, [rank1; 5]),
+            Order::Stfl => {
+                let suit = SUIT_MASKS.iter()
+                    .map(|(mask, offset)| (*offset as u32, rank_set(cards & (mask.clone() as u64))))
+                    .find(|(_, suited_ranks)| straight_ranks(rank1).iter().all(|rank| suited_ranks & (1 << (rank - 1)) != 0))
+                    .map(|(suit, _)| suit)
+                    .expect("Bug! Straight flush without five suited cards");
+                (suit, straight_ranks(rank1))
+            },
+        };
+
+        ShowdownHand::new(order, suit, &ranks)
+    }
+}
+
+impl ShowdownHand {
+    fn new(order: Order, suit: u32, ranks: &[u32]) -> ShowdownHand {
+        let ranks = ranks.iter()
+            .chain([0u32; 5].iter())
+            .take(5)
+            .fold(0, |acc, rank| acc << 4 | rank);
+        ShowdownHand((order as u32) << 28 | suit << 26 | ranks)
+    }
+
+    pub fn order(&self) -> Order {
+        Order::from_bits((self.0 >> 28) as u8)
+    }
+
+    /// The five card ranks in order of significance, zero where the hand has fewer than five cards.
+    pub fn ranks(&self) -> [u8; 5] {
+        let mut ranks = [0u8; 5];
+        for (i, rank) in ranks.iter_mut().enumerate() {
+            *rank = ((self.0 >> (16 - 4 * i)) & 0xf) as u8;
+        }
+        ranks
+    }
+
+    #[cfg(feature = "std")]
+    fn suit_bits(&self) -> u32 {
+        (self.0 >> 26) & 0b11
+    }
+
+    #[cfg(feature = "std")]
+    fn with_ranking(self, ranking: u32) -> ShowdownHand {
+        ShowdownHand(self.0 & !(0b11 << 24) | ranking << 24)
+    }
+
+    /// Comparable showdown value, the greater value wins and equal values tie. Short deck hands
+    /// compare by the short deck ranking of hand classes.
+    pub fn value(&self) -> u32 {
+        self.strength()
+    }
+
+    // the suit of a flush never breaks a tie at showdown
+    fn strength(&self) -> u32 {
+        let ranking = (self.0 >> 24) & 0b11;
+        ranked_order(ranking, self.0 >> 28) << 28 | (self.0 & 0x03ffffff)
+    }
+}
+
+// position of the order bits in the ranking of hand classes, see ShowdownHand
+const fn ranked_order(ranking: u32, order: u32) -> u32 {
+    const TRIP: u32 = Order::Trip as u32;
+    const STRT: u32 = Order::Strt as u32;
+    const FLSH: u32 = Order::Flsh as u32;
+    const BOAT: u32 = Order::Boat as u32;
+    match (ranking, order) {
+        (1 | 2, FLSH) => BOAT,
+        (1 | 2, BOAT) => FLSH,
+        (2, TRIP) => STRT,
+        (2, STRT) => TRIP,
+        _ => order,
+    }
+}
+
+impl Default for ShowdownHand {
+    fn default() -> ShowdownHand {
+        ShowdownHand::new(Order::None, 0, &[])
+    }
+}
+
+impl PartialEq for ShowdownHand {
+    fn eq(&self, other: &ShowdownHand) -> bool {
+        self.strength() == other.strength()
+    }
+}
+
+impl Eq for ShowdownHand {}
+
+impl PartialOrd for ShowdownHand {
+    fn partial_cmp(&self, other: &ShowdownHand) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ShowdownHand {
+    fn cmp(&self, other: &ShowdownHand) -> Ordering {
+        self.strength().cmp(&other.strength())
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ShowdownHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ranks: Vec<Rank> = self.ranks().iter().filter(|&&rank| rank != 0).map(|&rank| Rank::from_bits(rank)).collect();
+        match self.order() {
+            Order::Stfl if ranks[0] == Rank::_A => write!(f, "Royal flush"),
+            order => describe(f, &order, &ranks),
+        }
+    }
+}
+
+// wording of a hand class and its ranks in order of significance, shared with LowHand
+#[cfg(feature = "std")]
+fn describe(f: &mut fmt::Formatter, order: &Order, ranks: &[Rank]) -> fmt::Result {
+    match order {
+        Order::None => write!(f, "No hand"),
+        Order::High => write!(f, "{} high", capitalize(ranks[0].name())),
+        Order::Pair => write!(f, "Pair of {}", ranks[0].plural()),
+        Order::Twop => write!(f, "Two pair, {} and {}", ranks[0].plural(), ranks[2].plural()),
+        Order::Trip => write!(f, "Three {}", ranks[0].plural()),
+        Order::Strt => write!(f, "{}-high straight", capitalize(ranks[0].name())),
+        Order::Flsh => write!(f, "{}-high flush", capitalize(ranks[0].name())),
+        Order::Boat => write!(f, "{} full of {}", capitalize(ranks[0].plural()), ranks[3].plural()),
+        Order::Quad => write!(f, "Four {}", ranks[0].plural()),
+        Order::Stfl => write!(f, "{}-high straight flush", capitalize(ranks[0].name())),
+        Order::Fivk => write!(f, "Five {}", ranks[0].plural()),
+    }
+}
+
+// duplicate card error naming the lowest card in both sets of card bits
+#[cfg(feature = "std")]
+fn check_disjoint(a: u64, b: u64) -> Result<(), Error> {
+    match BuildingHand(a & b).cards().first() {
+        Some(card) => Err(error!("Duplicate card", "{}", card).kind(ErrorKind::DuplicateCard)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(feature = "std")]
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+// bit (rank - 1) set for every rank with at least one card in the hand
+fn rank_set(cards: u64) -> u32 {
+    SUITS.iter().fold(0, |ranks, suit| ranks | suit_ranks(cards, suit) as u32)
+}
+
+// like rank_set, shifted up one bit to make room for the ace at bit 0 where it plays low
+#[cfg(feature = "std")]
+fn wheel_rank_set(cards: u64) -> u32 {
+    let ranks = rank_set(cards);
+    ranks << 1 | ranks >> (Rank::_A as u32 - 1)
+}
+
+// ranks of one suit as a 13 bit set, deuce in bit 0, without branches or loops
+fn suit_ranks(cards: u64, suit: &Suit) -> u64 {
+    // gather every 4th bit into the low 13 bits
+    let mut x = (cards >> (suit.clone() as u64)) & 0x0001111111111111;
+    x = (x | x >> 3) & 0x0303030303030303;
+    x = (x | x >> 6) & 0x000f000f000f000f;
+    x = (x | x >> 12) & 0x000000ff000000ff;
+    (x | x >> 24) & 0x1fff
+}
+
+// ranks of every suit, see suit_ranks
+fn suit_masks(cards: u64) -> [u16; 4] {
+    SUITS.map(|suit| suit_ranks(cards, &suit) as u16)
+}
+
+// ranks with at least 1, 2, 3 and 4 cards out of the ranks of every suit
+fn rank_counts(suits: &[u16; 4]) -> [u16; 4] {
+    let [d, c, h, s] = *suits;
+    [
+        d | c | h | s,
+        (d & c) | (d & h) | (d & s) | (c & h) | (c & s) | (h & s),
+        (d & c & h) | (d & c & s) | (d & h & s) | (c & h & s),
+        d & c & h & s,
+    ]
+}
+
+// top rank of the best straight in the set of ranks, the ace plays low too
+fn straight_top(ranks: u16) -> Option<u64> {
+    let ranks = (ranks as u32) << 1 | (ranks as u32) >> 12;
+    let runs = ranks & ranks << 1 & ranks << 2 & ranks << 3 & ranks << 4;
+    msb(runs as u64).map(|bit| bit as u64)
+}
+
+// inverse of suit_ranks
+#[cfg(feature = "std")]
+fn spread(ranks: u64, suit: &Suit) -> u64 {
+    (0..13).fold(0, |cards, rank| cards | (((ranks >> rank) & 1) << ((rank << 2) + suit.clone() as u64)))
+}
+
+// the given ranks followed by the highest ranks of the set, five at most, 0 past the last rank
+fn top_ranks(given: &[u32], ranks: u32) -> [u32; 5] {
+    let mut top = [0; 5];
+    let highest = (1..14).rev().filter(|rank| ranks & (1 << (rank - 1)) != 0);
+    for (top, rank) in top.iter_mut().zip(given.iter().cloned().chain(highest)) {
+        *top = rank;
+    }
+    top
+}
+
+fn straight_ranks(top_rank: u32) -> [u32; 5] {
+    let mut ranks = [0; 5];
+    for (i, rank) in ranks.iter_mut().enumerate() {
+        *rank = if top_rank == Rank::_5 as u32 && i == 4 { Rank::_A as u32 } else { top_rank - i as u32 };
     }
+    ranks
 }
 
 #[repr(u8)]
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum Order {
-    None, High, Pair, Twop, Trip, Strt, Flsh, Boat, Quad, Stfl
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Order {
+    None, High, Pair, Twop, Trip, Strt, Flsh, Boat, Quad, Stfl, Fivk
+}
+
+impl Order {
+    fn from_bits(bits: u8) -> Order {
+        match bits & 0xf {
+             0 => Order::None,
+             1 => Order::High,
+             2 => Order::Pair,
+             3 => Order::Twop,
+             4 => Order::Trip,
+             5 => Order::Strt,
+             6 => Order::Flsh,
+             7 => Order::Boat,
+             8 => Order::Quad,
+             9 => Order::Stfl,
+            10 => Order::Fivk,
+            _ => panic!("Bug! Unknown order"),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Order::None => "nothing",
+            Order::High => "high card",
+            Order::Pair => "pair",
+            Order::Twop => "two pair",
+            Order::Trip => "three of a kind",
+            Order::Strt => "straight",
+            Order::Flsh => "flush",
+            Order::Boat => "full house",
+            Order::Quad => "four of a kind",
+            Order::Stfl => "straight flush",
+            Order::Fivk => "five of a kind",
+        }
+    }
 }
 
 #[repr(u64)]
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Card {
     _2d = 0x0000000000000001, _2c = 0x0000000000000002, _2h = 0x0000000000000004, _2s = 0x0000000000000008,
     _3d = 0x0000000000000010, _3c = 0x0000000000000020, _3h = 0x0000000000000040, _3s = 0x0000000000000080,
@@ -332,6 +790,50 @@ pub enum Card {
 
 const SUIT_MASKS: [(Card, u8); 4] = [(Card::_Md, 0), (Card::_Mc, 1), (Card::_Mh, 2), (Card::_Ms, 3)];
 
+const SUITS: [Suit; 4] = [Suit::_D, Suit::_C, Suit::_H, Suit::_S];
+
+const CARDS: [Card; 52] = [
+    Card::_2d, Card::_2c, Card::_2h, Card::_2s, Card::_3d, Card::_3c, Card::_3h, Card::_3s,
+    Card::_4d, Card::_4c, Card::_4h, Card::_4s, Card::_5d, Card::_5c, Card::_5h, Card::_5s,
+    Card::_6d, Card::_6c, Card::_6h, Card::_6s, Card::_7d, Card::_7c, Card::_7h, Card::_7s,
+    Card::_8d, Card::_8c, Card::_8h, Card::_8s, Card::_9d, Card::_9c, Card::_9h, Card::_9s,
+    Card::_Td, Card::_Tc, Card::_Th, Card::_Ts, Card::_Jd, Card::_Jc, Card::_Jh, Card::_Js,
+    Card::_Qd, Card::_Qc, Card::_Qh, Card::_Qs, Card::_Kd, Card::_Kc, Card::_Kh, Card::_Ks,
+    Card::_Ad, Card::_Ac, Card::_Ah, Card::_As,
+];
+
+/// Card like "As", either case.
+impl FromStr for Card {
+    type Err = Error;
+
+    fn from_str(card: &str) -> Result<Card, Error> {
+        match *card.as_bytes() {
+            [rank, suit] => Card::parse(rank, suit),
+            _ => None,
+        }.ok_or_else(|| error!("Invalid card", "{}", card).kind(ErrorKind::InvalidCard).at(0))
+    }
+}
+
+/// Cards like "As Ks Qs", spaces optional, same as BuildingHand::parse but with errors for
+/// invalid and duplicate cards, positioned at the card's rank.
+impl FromStr for BuildingHand {
+    type Err = Error;
+
+    fn from_str(cards: &str) -> Result<BuildingHand, Error> {
+        let mut hand = BuildingHand::new();
+        let mut chars = cards.bytes().enumerate().filter(|&(_, c)| c != b' ');
+        while let Some((position, rank)) = chars.next() {
+            match chars.next().and_then(|(_, suit)| Card::parse(rank, suit)) {
+                Some(card) => if let Some(e) = hand.add_card(&card, true) {
+                    return Err(e.at(position));
+                },
+                None => return Err(error!("Invalid cards", "{}", cards).kind(ErrorKind::InvalidCard).at(position)),
+            }
+        }
+        Ok(hand)
+    }
+}
+
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", match *self {
@@ -354,19 +856,58 @@ impl fmt::Display for Card {
 }
 
 #[repr(u8)]
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum Rank {
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Rank {
     _2 = 1, _3, _4, _5, _6, _7, _8, _9, _T, _J, _Q, _K, _A
 }
 
+impl Rank {
+    #[cfg(feature = "std")]
+    fn from_bits(bits: u8) -> Rank {
+        match bits {
+             1 => Rank::_2,
+             2 => Rank::_3,
+             3 => Rank::_4,
+             4 => Rank::_5,
+             5 => Rank::_6,
+             6 => Rank::_7,
+             7 => Rank::_8,
+             8 => Rank::_9,
+             9 => Rank::_T,
+            10 => Rank::_J,
+            11 => Rank::_Q,
+            12 => Rank::_K,
+            13 => Rank::_A,
+            _ => panic!("Bug! Unknown rank"),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Rank::_2 => "two", Rank::_3 => "three", Rank::_4 => "four", Rank::_5 => "five",
+            Rank::_6 => "six", Rank::_7 => "seven", Rank::_8 => "eight", Rank::_9 => "nine",
+            Rank::_T => "ten", Rank::_J => "jack", Rank::_Q => "queen", Rank::_K => "king",
+            Rank::_A => "ace",
+        }
+    }
+
+    pub fn plural(&self) -> &'static str {
+        match *self {
+            Rank::_2 => "twos", Rank::_3 => "threes", Rank::_4 => "fours", Rank::_5 => "fives",
+            Rank::_6 => "sixes", Rank::_7 => "sevens", Rank::_8 => "eights", Rank::_9 => "nines", Rank::_T => "tens",
+            Rank::_J => "jacks", Rank::_Q => "queens", Rank::_K => "kings", Rank::_A => "aces",
+        }
+    }
+}
+
 #[repr(u8)]
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum Suit {
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Suit {
     _D, _C, _H, _S
 }
 
 impl Card {
-    fn from(rank: Rank, suit: Suit) -> Card {
+    pub const fn from(rank: Rank, suit: Suit) -> Card {
         match (rank, suit) {
             (Rank::_2, Suit::_D) => Card::_2d, (Rank::_2, Suit::_C) => Card::_2c,
             (Rank::_2, Suit::_H) => Card::_2h, (Rank::_2, Suit::_S) => Card::_2s,
@@ -397,7 +938,22 @@ impl Card {
         }
     }
 
-    fn rank(&self) -> Rank {
+    // card of a rank and suit character like 'A' and 's', either case
+    const fn parse(rank: u8, suit: u8) -> Option<Card> {
+        let rank = match rank.to_ascii_uppercase() {
+            b'2' => Rank::_2, b'3' => Rank::_3, b'4' => Rank::_4, b'5' => Rank::_5, b'6' => Rank::_6,
+            b'7' => Rank::_7, b'8' => Rank::_8, b'9' => Rank::_9, b'T' => Rank::_T, b'J' => Rank::_J,
+            b'Q' => Rank::_Q, b'K' => Rank::_K, b'A' => Rank::_A,
+            _ => return None,
+        };
+        let suit = match suit.to_ascii_lowercase() {
+            b'd' => Suit::_D, b'c' => Suit::_C, b'h' => Suit::_H, b's' => Suit::_S,
+            _ => return None,
+        };
+        Some(Card::from(rank, suit))
+    }
+
+    pub fn rank(&self) -> Rank {
         match msb(self.clone() as u64).expect("Bug! Unknown card") / 4 {
              0 => Rank::_2,
              1 => Rank::_3,
@@ -416,7 +972,7 @@ impl Card {
         }
     }
 
-    fn suit(&self) -> Suit {
+    pub fn suit(&self) -> Suit {
         match lsb(self.clone() as u64).expect("Bug! Unknown card") % 4 {
             0 => Suit::_D,
             1 => Suit::_C,
@@ -427,32 +983,14 @@ impl Card {
     }
 }
 
+// leading_zeros and trailing_zeros compile to lzcnt/tzcnt (or bsr/bsf) where the target has
+// them, and to a portable bit twiddling sequence where it does not
 fn msb(x: u64) -> Option<u8> {
-    if x == 0 { return None; }
-
-    let mut b = 0;
-    let mut y = x;
-    if y & 0xffffffff00000000 != 0 { b += 32; y >>= 32; }
-    if y & 0x00000000ffff0000 != 0 { b += 16; y >>= 16; }
-    if y & 0x000000000000ff00 != 0 { b +=  8; y >>=  8; }
-    if y & 0x00000000000000f0 != 0 { b +=  4; y >>=  4; }
-    if y & 0x000000000000000c != 0 { b +=  2; y >>=  2; }
-    if y & 0x0000000000000002 != 0 { b +=  1; }
-    Some(b)
+    if x == 0 { None } else { Some(63 - x.leading_zeros() as u8) }
 }
 
 fn lsb(x: u64) -> Option<u8> {
-    if x == 0 { return None; }
-
-    let mut b = 0;
-    let mut y = x;
-    if y & 0xffffffff == 0 { b += 32; y >>= 32; }
-    if y & 0x0000ffff == 0 { b += 16; y >>= 16; }
-    if y & 0x000000ff == 0 { b +=  8; y >>=  8; }
-    if y & 0x0000000f == 0 { b +=  4; y >>=  4; }
-    if y & 0x00000003 == 0 { b +=  2; y >>=  2; }
-    if y & 0x00000001 == 0 { b +=  1; }
-    Some(b)
+    if x == 0 { None } else { Some(x.trailing_zeros() as u8) }
 }
 
 #[cfg(test)]
@@ -500,6 +1038,62 @@ mod tests {
         assert_eq!(Some(0), lsb(u64::MAX - 8));
     }
 
+    #[test]
+    fn hands_at_compile_time() {
+        const BOARD: BuildingHand = BuildingHand::new().with_card(Card::_Qs).with_card(Card::_Js).with_card(Card::_Ts);
+        let mut hand = BuildingHand::new();
+        for card in &[Card::_Qs, Card::_Js, Card::_Ts] {
+            hand.add_card(card, true);
+        }
+        assert_eq!(hand, BOARD);
+        assert_eq!(BOARD, hand!("Qs Js Ts"));
+        assert_eq!(BOARD, hand!("qSjsTS"));
+        assert_eq!(BuildingHand::new(), hand!(""));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn showdown_hand_descriptions() {
+        let describe = |hand: BuildingHand| hand.to_showdown_hand().to_string();
+        assert_eq!("Royal flush", describe(hand!("As Ks Qs Js Ts 2d")));
+        assert_eq!("Five-high straight flush", describe(hand!("Ah 2h 3h 4h 5h")));
+        assert_eq!("Kings full of nines", describe(hand!("Kd Kc Kh 9d 9c 9h")));
+        assert_eq!("Two pair, aces and twos", describe(hand!("Ad Ac 2d 2c")));
+        assert_eq!("Jack high", describe(hand!("Jd 9c 7h 4s 2d")));
+        assert_eq!("No hand", describe(BuildingHand::new()));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn best_five_cards() {
+        assert_eq!(hand!("As Ks Qs Js Ts"), hand!("2d As Ks Qs Js Ts 9s").best_five());
+        assert_eq!(hand!("7c 7d 2s 2h 2d"), hand!("7c 7d 2s 2h 2d 3c 4c").best_five());
+        assert_eq!(hand!("Ad 5c 4h 3s 2d"), hand!("Ad Kd 5c 4h 3s 2d").best_five());
+        assert_eq!(hand!("Ah Kh"), hand!("Ah Kh").best_five());
+        assert_eq!(BuildingHand::new(), BuildingHand::new().best_five());
+    }
+
+    #[test]
+    fn hands_from_strings() {
+        assert_eq!(Ok(Card::_As), "As".parse());
+        assert_eq!(Ok(Card::_Td), "tD".parse());
+        assert!("A".parse::<Card>().is_err());
+        assert!("Asx".parse::<Card>().is_err());
+
+        assert_eq!(Ok(hand!("As Ks 2d")), "As Ks 2d".parse());
+        assert_eq!(Ok(hand!("As Ks 2d")), "AsKs2d".parse());
+        assert_eq!(Ok(BuildingHand::new()), "".parse());
+        assert!("As Ks 2".parse::<BuildingHand>().is_err());
+        assert!("As Ks Xd".parse::<BuildingHand>().is_err());
+        let duplicate = "As Ks As".parse::<BuildingHand>().unwrap_err();
+        assert_eq!((ErrorKind::DuplicateCard, Some(6)), (duplicate.kind, duplicate.position));
+        #[cfg(feature = "std")]
+        assert_eq!("Duplicate card: As", duplicate.msg());
+        assert_eq!(Some(4), "AsKsXd".parse::<BuildingHand>().unwrap_err().position);
+        assert_eq!(ErrorKind::InvalidCard, "As Ks 2".parse::<BuildingHand>().unwrap_err().kind);
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn normalize_input_cards_with_quit() {
         assert_eq!(Ok(None), normalize_input_cards("done", 0));
@@ -510,6 +1104,7 @@ mod tests {
         assert_eq!(Ok(None), normalize_input_cards("QUIT", 0));
     }
 
+    #[cfg(feature = "std")]
     #[test]
     fn normalize_input_cards_with_invalid_input() {
         assert_eq!(Ok(None), normalize_input_cards("done", 0));
@@ -537,6 +1132,16 @@ mod tests {
         hand.add_card(&Card::_4d, true);
         hand.add_card(&Card::_3d, true);
         assert_eq!(hand.new_realized_hand(Order::Stfl, Rank::_5 as u64, Rank::_4 as u64), hand.to_realized_hand());
+
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_Ks, true);
+        hand.add_card(&Card::_Qs, true);
+        hand.add_card(&Card::_Qd, true);
+        hand.add_card(&Card::_Jd, true);
+        hand.add_card(&Card::_Td, true);
+        hand.add_card(&Card::_9d, true);
+        hand.add_card(&Card::_8d, true);
+        assert_eq!(hand.new_realized_hand(Order::Stfl, Rank::_Q as u64, Rank::_J as u64), hand.to_realized_hand());
     }
 
     #[test]
@@ -748,4 +1353,185 @@ mod tests {
         hand.add_card(&Card::_Qh, true);
         assert_eq!(hand.new_realized_hand(Order::High, Rank::_A as u64, Rank::_K as u64), hand.to_realized_hand());
     }
+
+    #[test]
+    fn building_hand_to_realized_hand_with_partial_hands() {
+
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_9h, true);
+        assert_eq!(hand.new_realized_hand(Order::High, Rank::_9 as u64, 0), hand.to_realized_hand());
+
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_9h, true);
+        hand.add_card(&Card::_9s, true);
+        assert_eq!(hand.new_realized_hand(Order::Pair, Rank::_9 as u64, 0), hand.to_realized_hand());
+
+        hand.add_card(&Card::_Kd, true);
+        assert_eq!(hand.new_realized_hand(Order::Pair, Rank::_9 as u64, Rank::_K as u64), hand.to_realized_hand());
+
+        hand.add_card(&Card::_Kc, true);
+        assert_eq!(hand.new_realized_hand(Order::Twop, Rank::_K as u64, Rank::_9 as u64), hand.to_realized_hand());
+
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_4d, true);
+        hand.add_card(&Card::_4c, true);
+        hand.add_card(&Card::_4h, true);
+        assert_eq!(hand.new_realized_hand(Order::Trip, Rank::_4 as u64, 0), hand.to_realized_hand());
+
+        hand.add_card(&Card::_4s, true);
+        assert_eq!(hand.new_realized_hand(Order::Quad, Rank::_4 as u64, 0), hand.to_realized_hand());
+
+        // four to a straight flush is still just high card
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_Ah, true);
+        hand.add_card(&Card::_2h, true);
+        hand.add_card(&Card::_3h, true);
+        hand.add_card(&Card::_4h, true);
+        assert_eq!(hand.new_realized_hand(Order::High, Rank::_A as u64, Rank::_4 as u64), hand.to_realized_hand());
+    }
+
+    #[test]
+    fn building_hand_to_showdown_hand_with_partial_hands() {
+
+        let mut trips = BuildingHand::new();
+        trips.add_card(&Card::_4d, true);
+        trips.add_card(&Card::_4c, true);
+        trips.add_card(&Card::_4h, true);
+        trips.add_card(&Card::_Js, true);
+        assert_eq!([Rank::_4 as u8, Rank::_4 as u8, Rank::_4 as u8, Rank::_J as u8, 0], trips.to_showdown_hand().ranks());
+
+        let mut high = BuildingHand::new();
+        high.add_card(&Card::_Ah, true);
+        high.add_card(&Card::_2h, true);
+        high.add_card(&Card::_3h, true);
+        assert_eq!(Order::High, high.to_showdown_hand().order());
+        assert_eq!([Rank::_A as u8, Rank::_3 as u8, Rank::_2 as u8, 0, 0], high.to_showdown_hand().ranks());
+
+        assert_eq!(Order::None, BuildingHand::new().to_showdown_hand().order());
+        assert_eq!([0; 5], BuildingHand::new().to_showdown_hand().ranks());
+    }
+
+    #[test]
+    fn showdown_hand_ordering_with_partial_hands() {
+
+        // a missing kicker loses to any kicker
+        let mut aces = BuildingHand::new();
+        aces.add_card(&Card::_Ah, true);
+        aces.add_card(&Card::_Ad, true);
+        let mut aces_deuce = aces.clone();
+        aces_deuce.add_card(&Card::_2s, true);
+        assert!(aces_deuce.to_showdown_hand() > aces.to_showdown_hand());
+
+        let mut kings = BuildingHand::new();
+        kings.add_card(&Card::_Kh, true);
+        kings.add_card(&Card::_Kd, true);
+        kings.add_card(&Card::_Qd, true);
+        assert!(aces.to_showdown_hand() > kings.to_showdown_hand());
+
+        let mut ace_king = BuildingHand::new();
+        ace_king.add_card(&Card::_Ah, true);
+        ace_king.add_card(&Card::_Kd, true);
+        let mut ace_queen_jack = BuildingHand::new();
+        ace_queen_jack.add_card(&Card::_As, true);
+        ace_queen_jack.add_card(&Card::_Qd, true);
+        ace_queen_jack.add_card(&Card::_Jd, true);
+        assert!(ace_king.to_showdown_hand() > ace_queen_jack.to_showdown_hand());
+        assert!(BuildingHand::new().to_showdown_hand() < ace_queen_jack.to_showdown_hand());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn building_hand_combinations() {
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_Kd, true);
+        hand.add_card(&Card::_8c, true);
+        hand.add_card(&Card::_9h, true);
+        hand.add_card(&Card::_Js, true);
+        hand.add_card(&Card::_Ad, true);
+        assert_eq!(1, hand.combinations(0).len());
+        assert_eq!(10, hand.combinations(3).len());
+        assert_eq!(vec![hand.clone()], hand.combinations(5));
+        assert!(hand.combinations(6).is_empty());
+        assert!(hand.combinations(2).iter().all(|pair| pair.len() == 2 && pair.0 & !hand.0 == 0));
+    }
+
+    #[test]
+    fn building_hand_to_showdown_hand_with_kickers() {
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_Kd, true);
+        hand.add_card(&Card::_8c, true);
+        hand.add_card(&Card::_9h, true);
+        hand.add_card(&Card::_Js, true);
+        hand.add_card(&Card::_Ad, true);
+        hand.add_card(&Card::_8d, true);
+        hand.add_card(&Card::_Qh, true);
+        let showdown = hand.to_showdown_hand();
+        assert_eq!(Order::Pair, showdown.order());
+        assert_eq!([Rank::_8 as u8, Rank::_8 as u8, Rank::_A as u8, Rank::_K as u8, Rank::_Q as u8], showdown.ranks());
+
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_As, true);
+        hand.add_card(&Card::_Ac, true);
+        hand.add_card(&Card::_6c, true);
+        hand.add_card(&Card::_5c, true);
+        hand.add_card(&Card::_6d, true);
+        hand.add_card(&Card::_Jh, true);
+        hand.add_card(&Card::_Jc, true);
+        let showdown = hand.to_showdown_hand();
+        assert_eq!(Order::Twop, showdown.order());
+        assert_eq!([Rank::_A as u8, Rank::_A as u8, Rank::_J as u8, Rank::_J as u8, Rank::_6 as u8], showdown.ranks());
+
+        let mut hand = BuildingHand::new();
+        hand.add_card(&Card::_Ac, true);
+        hand.add_card(&Card::_Td, true);
+        hand.add_card(&Card::_3c, true);
+        hand.add_card(&Card::_4s, true);
+        hand.add_card(&Card::_Ad, true);
+        hand.add_card(&Card::_2c, true);
+        hand.add_card(&Card::_5c, true);
+        let showdown = hand.to_showdown_hand();
+        assert_eq!(Order::Strt, showdown.order());
+        assert_eq!([Rank::_5 as u8, Rank::_4 as u8, Rank::_3 as u8, Rank::_2 as u8, Rank::_A as u8], showdown.ranks());
+    }
+
+    #[test]
+    fn showdown_hand_ordering() {
+        let mut flush = BuildingHand::new();
+        flush.add_card(&Card::_Ac, true);
+        flush.add_card(&Card::_9c, true);
+        flush.add_card(&Card::_7c, true);
+        flush.add_card(&Card::_4c, true);
+        flush.add_card(&Card::_2c, true);
+
+        let mut other_flush = BuildingHand::new();
+        other_flush.add_card(&Card::_Ah, true);
+        other_flush.add_card(&Card::_9h, true);
+        other_flush.add_card(&Card::_7h, true);
+        other_flush.add_card(&Card::_4h, true);
+        other_flush.add_card(&Card::_2h, true);
+        assert_eq!(flush.to_showdown_hand(), other_flush.to_showdown_hand());
+
+        let mut straight = BuildingHand::new();
+        straight.add_card(&Card::_Ah, true);
+        straight.add_card(&Card::_Kc, true);
+        straight.add_card(&Card::_Qd, true);
+        straight.add_card(&Card::_Js, true);
+        straight.add_card(&Card::_Th, true);
+        assert!(flush.to_showdown_hand() > straight.to_showdown_hand());
+
+        let mut pair_kicker = BuildingHand::new();
+        pair_kicker.add_card(&Card::_8h, true);
+        pair_kicker.add_card(&Card::_8c, true);
+        pair_kicker.add_card(&Card::_Ad, true);
+        pair_kicker.add_card(&Card::_9s, true);
+        pair_kicker.add_card(&Card::_3h, true);
+
+        let mut pair_weaker_kicker = BuildingHand::new();
+        pair_weaker_kicker.add_card(&Card::_8d, true);
+        pair_weaker_kicker.add_card(&Card::_8s, true);
+        pair_weaker_kicker.add_card(&Card::_Ac, true);
+        pair_weaker_kicker.add_card(&Card::_9d, true);
+        pair_weaker_kicker.add_card(&Card::_2h, true);
+        assert!(pair_kicker.to_showdown_hand() > pair_weaker_kicker.to_showdown_hand());
+    }
 }
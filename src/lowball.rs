@@ -0,0 +1,219 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::{capitalize, describe, BuildingHand, Order, Rank};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Lowball {
+    AceToFive,    // Razz: aces are low, straights and flushes don't count
+    DeuceToSeven, // aces are high, straights and flushes count against the hand
+}
+
+/* |....|.|.......|....|....|....|....|....|
+ * -   4 bits: made hand classifier under the lowball rules, see enum Order
+ * -   1 bit:  lowball rules, 0 for ace-to-five, 1 for deuce-to-seven
+ * -   7 bits: TBD
+ * - 5x4 bits: card ranks in descending order from msb to lsb, aces are 1 in ace-to-five
+ *
+ * The raw value orders like a high hand, so the best low is the smallest value, except that no
+ * low at all, an empty hand, is the worst.
+ */
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LowHand(u32);
+
+impl BuildingHand {
+    /// Best five card low of the hand, or of all its cards when there are fewer than five.
+    pub fn to_low_hand(&self, lowball: &Lowball) -> LowHand {
+        self.combinations(self.len().min(5))
+            .iter()
+            .map(|hand| LowHand::of(hand, lowball))
+            .max()
+            .expect("Bug! No combinations of hand")
+    }
+}
+
+impl LowHand {
+    fn of(hand: &BuildingHand, lowball: &Lowball) -> LowHand {
+        match lowball {
+            Lowball::AceToFive => {
+                let mut counts = [0u8; 14];
+                for card in hand.cards() {
+                    counts[ace_to_five_rank(card.rank() as u32) as usize] += 1;
+                }
+
+                // group by count first, so pairs and trips lead their kickers
+                let mut ranks: Vec<(u8, u32)> = (1..14)
+                    .filter(|&rank| counts[rank] > 0)
+                    .map(|rank| (counts[rank], rank as u32))
+                    .collect();
+                ranks.sort_by(|a, b| b.cmp(a));
+
+                let order = match ranks.iter().map(|&(count, _)| count).collect::<Vec<_>>().as_slice() {
+                    [] => Order::None,
+                    [4, ..] => Order::Quad,
+                    [3, 2] => Order::Boat,
+                    [3, ..] => Order::Trip,
+                    [2, 2, ..] => Order::Twop,
+                    [2, ..] => Order::Pair,
+                    _ => Order::High,
+                };
+
+                let ranks = ranks.iter()
+                    .flat_map(|&(count, rank)| vec![rank; count as usize])
+                    .chain([0u32; 5].iter().cloned())
+                    .take(5)
+                    .fold(0, |acc, rank| acc << 4 | rank);
+                LowHand((order as u32) << 28 | ranks)
+            },
+
+            Lowball::DeuceToSeven => {
+                let showdown = hand.to_showdown_hand();
+                let (order, ranks) = match (showdown.order(), showdown.ranks()) {
+                    // the ace only plays high, so a wheel is just ace high
+                    (Order::Strt, [4, ..]) => (Order::High, [13, 4, 3, 2, 1]),
+                    (Order::Stfl, [4, ..]) => (Order::Flsh, [13, 4, 3, 2, 1]),
+                    (order, ranks) => (order, ranks),
+                };
+
+                let ranks = ranks.iter().fold(0, |acc, &rank| acc << 4 | rank as u32);
+                LowHand((order as u32) << 28 | 1 << 27 | ranks)
+            },
+        }
+    }
+
+    pub fn order(&self) -> Order {
+        Order::from_bits((self.0 >> 28) as u8)
+    }
+
+    pub fn lowball(&self) -> Lowball {
+        if self.0 & 1 << 27 == 0 { Lowball::AceToFive } else { Lowball::DeuceToSeven }
+    }
+
+    /// The card ranks in order of significance, at most five.
+    pub fn ranks(&self) -> Vec<Rank> {
+        (0..5)
+            .map(|i| (self.0 >> (16 - 4 * i)) & 0xf)
+            .filter(|&rank| rank != 0)
+            .map(|rank| match self.lowball() {
+                Lowball::AceToFive if rank == 1 => Rank::_A,
+                Lowball::AceToFive => Rank::from_bits(rank as u8 - 1),
+                Lowball::DeuceToSeven => Rank::from_bits(rank as u8),
+            })
+            .collect()
+    }
+}
+
+// better lows compare greater, like ShowdownHand, and no low is worse than any low
+impl PartialOrd for LowHand {
+    fn partial_cmp(&self, other: &LowHand) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LowHand {
+    fn cmp(&self, other: &LowHand) -> Ordering {
+        let no_low = |hand: &LowHand| hand.order() == Order::None;
+        no_low(other).cmp(&no_low(self))
+            .then_with(|| other.0.cmp(&self.0))
+    }
+}
+
+impl fmt::Display for LowHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ranks = self.ranks();
+        match self.order() {
+            Order::None => write!(f, "No low"),
+            Order::High if ranks.len() == 1 => write!(f, "{} low", capitalize(ranks[0].name())),
+            Order::High => write!(f, "{}-{} low", capitalize(ranks[0].name()), ranks[1].name()),
+            order => describe(f, &order, &ranks),
+        }
+    }
+}
+
+// aces become the lowest rank, everything else moves up one
+fn ace_to_five_rank(rank: u32) -> u32 {
+    if rank == Rank::_A as u32 { 1 } else { rank + 1 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand;
+
+    #[test]
+    fn ace_to_five_ignores_straights_and_flushes() {
+        let wheel = hand!("As 2s 3s 4s 5s");
+        let low = wheel.to_low_hand(&Lowball::AceToFive);
+        assert_eq!(Order::High, low.order());
+        assert_eq!(vec![Rank::_5, Rank::_4, Rank::_3, Rank::_2, Rank::_A], low.ranks());
+        assert_eq!("Five-four low", low.to_string());
+
+        let six_low = hand!("6d 4c 3h 2s Ad");
+        assert!(low > six_low.to_low_hand(&Lowball::AceToFive));
+    }
+
+    #[test]
+    fn ace_to_five_picks_best_five_of_seven() {
+        let razz = hand!("Kd 7c 5h 5s 3d 2c Ah");
+        let low = razz.to_low_hand(&Lowball::AceToFive);
+        assert_eq!(vec![Rank::_7, Rank::_5, Rank::_3, Rank::_2, Rank::_A], low.ranks());
+        assert_eq!("Seven-five low", low.to_string());
+
+        // four distinct ranks, so the hand has to play a pair
+        let paired = hand!("2d 2c 3h 3s 4d 4c Ah");
+        let low = paired.to_low_hand(&Lowball::AceToFive);
+        assert_eq!(Order::Pair, low.order());
+        assert_eq!(vec![Rank::_2, Rank::_2, Rank::_4, Rank::_3, Rank::_A], low.ranks());
+        assert!(low < razz.to_low_hand(&Lowball::AceToFive));
+    }
+
+    #[test]
+    fn deuce_to_seven_counts_straights_and_flushes() {
+        let number_one = hand!("7d 5c 4h 3s 2d");
+        let low = number_one.to_low_hand(&Lowball::DeuceToSeven);
+        assert_eq!(Order::High, low.order());
+        assert_eq!("Seven-five low", low.to_string());
+
+        let straight = hand!("6d 5c 4h 3s 2d");
+        assert_eq!(Order::Strt, straight.to_low_hand(&Lowball::DeuceToSeven).order());
+        assert!(low > straight.to_low_hand(&Lowball::DeuceToSeven));
+
+        let flush = hand!("8d 5d 4d 3d 2d");
+        assert_eq!(Order::Flsh, flush.to_low_hand(&Lowball::DeuceToSeven).order());
+
+        let ace_high = hand!("Ad Kc Qh Js 9d");
+        assert!(flush.to_low_hand(&Lowball::DeuceToSeven) < ace_high.to_low_hand(&Lowball::DeuceToSeven));
+    }
+
+    #[test]
+    fn deuce_to_seven_plays_wheel_as_ace_high() {
+        let wheel = hand!("Ad 5c 4h 3s 2d");
+        let low = wheel.to_low_hand(&Lowball::DeuceToSeven);
+        assert_eq!(Order::High, low.order());
+        assert_eq!("Ace-five low", low.to_string());
+
+        let king_high = hand!("Kd Qc Jh Ts 8d");
+        assert!(low < king_high.to_low_hand(&Lowball::DeuceToSeven));
+    }
+
+    #[test]
+    fn no_low_is_worst() {
+        for lowball in [Lowball::AceToFive, Lowball::DeuceToSeven].iter() {
+            let none = BuildingHand::new().to_low_hand(lowball);
+            assert_eq!(Order::None, none.order());
+            assert_eq!("No low", none.to_string());
+            assert!(none < hand!("Kd").to_low_hand(lowball));
+            assert!(none < hand!("Kd Kc Kh Ks").to_low_hand(lowball));
+            assert_eq!(Ordering::Equal, none.cmp(&BuildingHand::new().to_low_hand(lowball)));
+        }
+    }
+
+    #[test]
+    fn deuce_to_seven_picks_best_five_of_seven() {
+        let seven = hand!("Kd 8c 7h 6s 5d 4c 3h");
+        let low = seven.to_low_hand(&Lowball::DeuceToSeven);
+        assert_eq!(Order::High, low.order());
+        assert_eq!(vec![Rank::_8, Rank::_6, Rank::_5, Rank::_4, Rank::_3], low.ranks());
+        assert_eq!("Eight-six low", low.to_string());
+    }
+}
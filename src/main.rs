@@ -1,81 +1,622 @@
-use std::collections::HashMap;
-use std::io;
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::process;
 
-use poker;
+const USAGE: &str = "Usage:
+  poker                                             interactive hand builder
+  poker eval <cards>                                best hand of the cards, e.g. poker eval \"AhKh QhJhTh\"
+  poker equity <hand> <hand>... [--board <cards>] [--dead <cards>]
+                                                    all-in equity of two card hands
+  poker batch [<file>]                              evaluate one hand per line of the file, or stdin
+
+Options:
+  --format text|json                                output of eval, equity and batch, text by default,
+                                                    json for results and errors as JSON on stdout,
+                                                    one object per line in batch";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+// error of a command, with the input it was parsing if any
+struct Failure {
+    kind: &'static str,
+    msg: String,
+    input: Option<String>,
+    position: Option<usize>, // byte offset in the input
+}
+
+impl Failure {
+    fn usage(msg: String) -> Failure {
+        Failure { kind: "usage", msg, input: None, position: None }
+    }
+
+    fn io(msg: String) -> Failure {
+        Failure { kind: "io", msg, input: None, position: None }
+    }
+
+    fn parsing(input: &str) -> impl Fn(poker::Error) -> Failure + '_ {
+        move |e| Failure { input: Some(input.to_owned()), ..Failure::from(e) }
+    }
+
+    fn json(&self) -> String {
+        format!(
+            "{{\"kind\":{},\"message\":{},\"input\":{},\"position\":{}}}",
+            json_string(self.kind),
+            json_string(&self.msg),
+            self.input.as_deref().map_or("null".to_owned(), json_string),
+            self.position.map_or("null".to_owned(), |position| position.to_string()),
+        )
+    }
+}
+
+impl From<poker::Error> for Failure {
+    fn from(e: poker::Error) -> Failure {
+        let kind = match e.kind {
+            poker::ErrorKind::InvalidCard => "invalid_card",
+            poker::ErrorKind::DuplicateCard => "duplicate_card",
+            poker::ErrorKind::InvalidInput => "invalid_input",
+        };
+        Failure { kind, msg: e.msg().to_owned(), input: None, position: e.position }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            "usage" => write!(f, "[ERROR] {}\n{}", self.msg, USAGE),
+            _ => write!(f, "[ERROR] {}", self.msg),
+        }
+    }
+}
 
 fn main() {
-    println!("\nPoker hand builder!");
-
-    //println!("\nThread city baby!");
-    //return;
-
-    let card_repr: HashMap<_, _> = [
-        ("2d", poker::Card::_2d), ("2c", poker::Card::_2c), ("2h", poker::Card::_2h), ("2s", poker::Card::_2s),
-        ("3d", poker::Card::_3d), ("3c", poker::Card::_3c), ("3h", poker::Card::_3h), ("3s", poker::Card::_3s),
-        ("4d", poker::Card::_4d), ("4c", poker::Card::_4c), ("4h", poker::Card::_4h), ("4s", poker::Card::_4s),
-        ("5d", poker::Card::_5d), ("5c", poker::Card::_5c), ("5h", poker::Card::_5h), ("5s", poker::Card::_5s),
-        ("6d", poker::Card::_6d), ("6c", poker::Card::_6c), ("6h", poker::Card::_6h), ("6s", poker::Card::_6s),
-        ("7d", poker::Card::_7d), ("7c", poker::Card::_7c), ("7h", poker::Card::_7h), ("7s", poker::Card::_7s),
-        ("8d", poker::Card::_8d), ("8c", poker::Card::_8c), ("8h", poker::Card::_8h), ("8s", poker::Card::_8s),
-        ("9d", poker::Card::_9d), ("9c", poker::Card::_9c), ("9h", poker::Card::_9h), ("9s", poker::Card::_9s),
-        ("Td", poker::Card::_Td), ("Tc", poker::Card::_Tc), ("Th", poker::Card::_Th), ("Ts", poker::Card::_Ts),
-        ("Jd", poker::Card::_Jd), ("Jc", poker::Card::_Jc), ("Jh", poker::Card::_Jh), ("Js", poker::Card::_Js),
-        ("Qd", poker::Card::_Qd), ("Qc", poker::Card::_Qc), ("Qh", poker::Card::_Qh), ("Qs", poker::Card::_Qs),
-        ("Kd", poker::Card::_Kd), ("Kc", poker::Card::_Kc), ("Kh", poker::Card::_Kh), ("Ks", poker::Card::_Ks),
-        ("Ad", poker::Card::_Ad), ("Ac", poker::Card::_Ac), ("Ah", poker::Card::_Ah), ("As", poker::Card::_As),
-    ].iter()
-        .map(|(s, card)| ((*s).to_owned(), card.clone()))
-        .collect();
-
-    let streets = [("preflop", 2), ("flop", 3), ("turn", 1), ("river", 1)];
-    let mut building_hand = poker::BuildingHand::new();
-
-    for &(street, ncards) in streets.iter() {
-        loop {
-            let mut input = String::new();
-            println!("\nAdd {} {} card(s):", ncards, street);
-            io::stdin()
-                .read_line(&mut input)
-                .expect("Failed to read input");
-
-            let cards = match poker::normalize_input_cards(&input, ncards) {
-                Ok(Some(cards)) => cards,
-                Ok(None) => {
-                    println!();
-                    return;
-                },
-                Err(e) => {
-                    println!("{}", e.msg);
-                    continue;
-                },
-            };
-
-            println!("Cards input: {}", cards.join(" "));
-            let cards: Vec<_> = cards.iter()
-                .map(|card| card_repr.get(card).expect("Bug! Unknown card mapping"))
-                .collect();
-
-            let mut street_hand = building_hand.clone();
-            let mut errors = 0;
-
-            for card in cards {
-                errors += match street_hand.add_card(card, true) {
-                    Some(e) => {
-                        println!("{}", e.msg);
-                        1
-                    },
-                    None => 0,
-                }
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let format = take_format(&mut args).unwrap_or_else(|e| fail(&e, Format::Text));
+    let result = match args.first().map(String::as_str) {
+        None if format == Format::Text => {
+            build_hand();
+            Ok(())
+        },
+        None => Err(Failure::usage("No JSON output for the interactive hand builder".to_owned())),
+        Some("eval") => eval(&args[1..], format),
+        Some("equity") => equity(&args[1..], format),
+        Some("batch") => batch(&args[1..], format),
+        Some("help") | Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        Some(command) => Err(Failure::usage(format!("Unknown command: {}", command))),
+    };
+
+    if let Err(e) = result {
+        fail(&e, format);
+    }
+}
+
+// JSON errors go to stdout along with the results
+fn fail(e: &Failure, format: Format) -> ! {
+    match format {
+        Format::Text => eprintln!("{}", e),
+        Format::Json => println!("{{\"error\":{}}}", e.json()),
+    }
+    process::exit(1);
+}
+
+// takes --format <format> or --format=<format> out of the arguments
+fn take_format(args: &mut Vec<String>) -> Result<Format, Failure> {
+    let position = match args.iter().position(|arg| arg == "--format" || arg.starts_with("--format=")) {
+        Some(position) => position,
+        None => return Ok(Format::Text),
+    };
+    let arg = args.remove(position);
+    let format = match arg.strip_prefix("--format=") {
+        Some(format) => format.to_owned(),
+        None if position < args.len() => args.remove(position),
+        None => return Err(Failure::usage("Missing format after --format".to_owned())),
+    };
+    match format.as_str() {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(Failure::usage(format!("Unknown format: {}", format))),
+    }
+}
+
+// best hand of all the cards given, in one argument or many
+fn eval(args: &[String], format: Format) -> Result<(), Failure> {
+    if args.is_empty() {
+        return Err(Failure::usage("Missing cards to evaluate".to_owned()));
+    }
+    let cards = args.join(" ");
+    println!("{}", evaluate(&cards, format).map_err(Failure::parsing(&cards))?);
+    Ok(())
+}
+
+fn evaluate(cards: &str, format: Format) -> Result<String, poker::Error> {
+    let hand: poker::BuildingHand = cards.parse()?;
+    let showdown = hand.to_showdown_hand();
+    let cards = card_names(&hand);
+    Ok(match (format, showdown.class()) {
+        (Format::Json, class) => format!(
+            "{{\"cards\":{},\"category\":{},\"description\":{},\"class\":{},\"value\":{}}}",
+            json_strings(&cards),
+            json_string(showdown.order().name()),
+            json_string(&showdown.to_string()),
+            class.map_or("null".to_owned(), |class| class.to_string()),
+            showdown.value(),
+        ),
+        (Format::Text, Some(class)) => format!("{}: {} (class {} of {})", cards.join(" "), showdown, class, poker::HAND_CLASSES),
+        (Format::Text, None) => format!("{}: {}", cards.join(" "), showdown),
+    })
+}
+
+fn equity(args: &[String], format: Format) -> Result<(), Failure> {
+    let mut hands = Vec::new();
+    let mut board = poker::BuildingHand::new();
+    let mut dead = poker::BuildingHand::new();
+
+    // every card is known once, a repeat is reported in the argument that repeats it
+    let mut known = poker::BuildingHand::new();
+    let mut options = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let input = match arg.as_str() {
+            "--board" | "--dead" if options.contains(&arg) => return Err(Failure::usage(format!("Repeated {}", arg))),
+            "--board" | "--dead" => {
+                options.push(arg);
+                args.next().ok_or_else(|| Failure::usage(format!("Missing cards after {}", arg)))?
+            },
+            _ => arg,
+        };
+        let cards: poker::BuildingHand = input.parse().map_err(Failure::parsing(input))?;
+        if let Some(card) = poker::BuildingHand::from_bits(known.bits() & cards.bits()).cards().first() {
+            let msg = format!("Duplicate card: {}", card);
+            return Err(Failure { kind: "duplicate_card", msg, input: Some(input.to_owned()), position: card_position(input, card) });
+        }
+        known = poker::BuildingHand::from_bits(known.bits() | cards.bits());
+
+        match arg.as_str() {
+            "--board" => board = cards,
+            "--dead" => dead = cards,
+            hand => hands.push((hand, cards)),
+        }
+    }
+
+    let holdings: Vec<poker::BuildingHand> = hands.iter().map(|(_, hand)| hand.clone()).collect();
+    let equities = poker::equity(&holdings, &board, &dead)?;
+    if format == Format::Text {
+        for ((name, _), equity) in hands.iter().zip(equities) {
+            println!("{}: {:.2}% equity, {:.2}% win, {:.2}% tie", name, 100.0 * equity.equity, 100.0 * equity.win(), 100.0 * equity.tie());
+        }
+        return Ok(());
+    }
+
+    let hands: Vec<String> = holdings.iter().zip(equities).map(|(hand, equity)| format!(
+        "{{\"hand\":{},\"equity\":{},\"win\":{},\"tie\":{},\"wins\":{},\"ties\":{},\"boards\":{}}}",
+        json_strings(&card_names(hand)), equity.equity, equity.win(), equity.tie(), equity.wins, equity.ties, equity.boards,
+    )).collect();
+    println!(
+        "{{\"board\":{},\"dead\":{},\"hands\":[{}]}}",
+        json_strings(&card_names(&board)), json_strings(&card_names(&dead)), hands.join(","),
+    );
+    Ok(())
+}
+
+// byte offset of the card's rank in cards that parse, see BuildingHand's FromStr
+fn card_position(cards: &str, card: &poker::Card) -> Option<usize> {
+    let mut chars = cards.char_indices().filter(|&(_, c)| c != ' ');
+    while let Some((position, rank)) = chars.next() {
+        let (_, suit) = chars.next()?;
+        if format!("{}{}", rank, suit).parse().as_ref() == Ok(card) {
+            return Some(position);
+        }
+    }
+    None
+}
+
+// one hand per line, blank lines and # comments skipped, errors reported per line
+fn batch(args: &[String], format: Format) -> Result<(), Failure> {
+    let input: Box<dyn BufRead> = match args.first().map(String::as_str) {
+        None | Some("-") => Box::new(BufReader::new(io::stdin())),
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => return Err(Failure::io(format!("Can't open {}: {}", path, e))),
+        },
+    };
+
+    let mut errors = 0;
+    for (number, line) in input.lines().enumerate() {
+        let line = line.map_err(|e| Failure::io(format!("Can't read line {}: {}", number + 1, e)))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match (evaluate(line, format).map_err(Failure::parsing(line)), format) {
+            (Ok(result), Format::Text) => println!("{}", result),
+            (Ok(result), Format::Json) => println!("{{\"line\":{},\"hand\":{}}}", number + 1, result),
+            (Err(e), Format::Text) => {
+                eprintln!("{} (line {})", e, number + 1);
+                errors += 1;
+            },
+            (Err(e), Format::Json) => {
+                println!("{{\"line\":{},\"error\":{}}}", number + 1, e.json());
+                errors += 1;
+            },
+        }
+    }
+
+    if errors > 0 {
+        return Err(Failure { kind: "invalid_input", msg: format!("{} invalid line(s)", errors), input: None, position: None });
+    }
+    Ok(())
+}
+
+// cards of the hand, highest first
+fn card_names(hand: &poker::BuildingHand) -> Vec<String> {
+    hand.cards().iter().rev().map(|card| card.to_string()).collect()
+}
+
+fn json_string(string: &str) -> String {
+    let mut json = String::from("\"");
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_strings(strings: &[String]) -> String {
+    let strings: Vec<String> = strings.iter().map(|string| json_string(string)).collect();
+    format!("[{}]", strings.join(","))
+}
+
+const COMMANDS: &str = "Commands:
+  <cards>                       cards of the next street, e.g. AhKh
+  undo                          take back the last street
+  reset                         start a new hand, without dead cards
+  show                          variant, streets, dead cards and the hand so far
+  dead [<cards>]                cards out of the deck, none without cards
+  variant holdem|shortdeck|omaha|stud
+                                game to play, with its streets, starting a new hand
+  streets <name>:<cards>[:hole]...
+                                streets to deal, hole for the player's own cards, starting a new hand,
+                                e.g. streets preflop:4:hole flop:3 turn:1 river:1
+  help                          these commands
+  done, exit, quit              leave";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Game {
+    Holdem,
+    ShortDeck,
+    Omaha, // exactly two hole cards and three board cards
+    Stud,
+}
+
+impl Game {
+    fn parse(name: &str) -> Option<Game> {
+        match name.to_lowercase().as_str() {
+            "holdem" | "hold'em" => Some(Game::Holdem),
+            "shortdeck" | "short-deck" => Some(Game::ShortDeck),
+            "omaha" => Some(Game::Omaha),
+            "stud" => Some(Game::Stud),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Game::Holdem => "holdem",
+            Game::ShortDeck => "shortdeck",
+            Game::Omaha => "omaha",
+            Game::Stud => "stud",
+        }
+    }
+
+    fn variant(&self) -> poker::Variant {
+        match self {
+            Game::ShortDeck => poker::Variant::ShortDeck(poker::ShortDeckRules::default()),
+            _ => poker::Variant::Holdem,
+        }
+    }
+
+    fn streets(&self) -> Vec<Street> {
+        let specs: &[(&str, usize, bool)] = match self {
+            Game::Holdem | Game::ShortDeck => &[("preflop", 2, true), ("flop", 3, false), ("turn", 1, false), ("river", 1, false)],
+            Game::Omaha => &[("preflop", 4, true), ("flop", 3, false), ("turn", 1, false), ("river", 1, false)],
+            Game::Stud => &[("third", 3, true), ("fourth", 1, true), ("fifth", 1, true), ("sixth", 1, true), ("seventh", 1, true)],
+        };
+        specs.iter()
+            .map(|&(name, cards, hole)| Street { name: name.to_owned(), cards, hole })
+            .collect()
+    }
+}
+
+struct Street {
+    name: String,
+    cards: usize,
+    hole: bool, // the player's own cards rather than the board
+}
+
+impl Street {
+    // name:cards, or name:cards:hole for the player's own cards
+    fn parse(spec: &str) -> Result<Street, String> {
+        let invalid = || format!("Invalid street, name:cards or name:cards:hole required: {}", spec);
+        let (name, cards, hole) = match *spec.split(':').collect::<Vec<_>>() {
+            [name, cards] => (name, cards, false),
+            [name, cards, "hole"] => (name, cards, true),
+            _ => return Err(invalid()),
+        };
+        match cards.parse() {
+            Ok(cards) if cards > 0 && !name.is_empty() => Ok(Street { name: name.to_owned(), cards, hole }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Street {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}{}", self.name, self.cards, if self.hole { " (hole)" } else { "" })
+    }
+}
+
+// state of the interactive hand builder
+struct Builder {
+    game: Game,
+    streets: Vec<Street>,
+    dealt: Vec<poker::BuildingHand>, // cards of each street dealt so far
+    dead: poker::BuildingHand,
+}
+
+impl Builder {
+    fn new(game: Game) -> Builder {
+        Builder { game, streets: game.streets(), dealt: Vec::new(), dead: poker::BuildingHand::new() }
+    }
+
+    fn next_street(&self) -> Option<&Street> {
+        self.streets.get(self.dealt.len())
+    }
+
+    // hole cards and board of the first streets dealt
+    fn split(&self, streets: usize) -> (poker::BuildingHand, poker::BuildingHand) {
+        let mut hole = poker::BuildingHand::new();
+        let mut board = poker::BuildingHand::new();
+        for (street, cards) in self.streets.iter().zip(&self.dealt[..streets]) {
+            if street.hole {
+                hole = union(&hole, cards);
+            } else {
+                board = union(&board, cards);
             }
+        }
+        (hole, board)
+    }
 
-            if errors > 0 {
-                continue;
+    fn deal(&mut self, input: &str) -> Result<(), String> {
+        let street = self.next_street().ok_or("Hand complete, undo, reset or quit")?;
+        let cards = match poker::normalize_input_cards(input, street.cards) {
+            Ok(Some(cards)) => cards,
+            Ok(None) => unreachable!("Bug! Quit taken for cards"),
+            Err(e) => return Err(e.msg().to_owned()),
+        };
+        let (hole, board) = self.split(self.dealt.len());
+        let mut hand = union(&hole, &board);
+        let mut street_cards = poker::BuildingHand::new();
+        for card in &cards {
+            let card = card.parse().expect("Bug! Unknown card mapping");
+            self.check_in_deck(&card)?;
+            if self.dead.contains(&card) {
+                return Err(format!("Dead card: {}", card));
+            }
+            if let Some(e) = hand.add_card(&card, true) {
+                return Err(e.msg().to_owned());
             }
+            street_cards.add_card(&card, false);
+        }
+        println!("Cards input: {}", cards.join(" "));
+        self.dealt.push(street_cards);
+        Ok(())
+    }
 
-            building_hand = street_hand;
+    fn check_in_deck(&self, card: &poker::Card) -> Result<(), String> {
+        if self.game.variant().deck().contains(card) {
+            Ok(())
+        } else {
+            Err(format!("Card not in a {} deck: {}", self.game.name(), card))
+        }
+    }
+
+    fn set_dead(&mut self, cards: &str) -> Result<(), String> {
+        let dead: poker::BuildingHand = cards.parse().map_err(|e: poker::Error| e.msg().to_owned())?;
+        let (hole, board) = self.split(self.dealt.len());
+        for card in dead.cards() {
+            self.check_in_deck(&card)?;
+            if hole.contains(&card) || board.contains(&card) {
+                return Err(format!("Card already dealt: {}", card));
+            }
+        }
+        self.dead = dead;
+        Ok(())
+    }
+
+    fn set_streets(&mut self, specs: &[&str]) -> Result<(), String> {
+        let streets = match specs {
+            [] => return Err("Missing streets".to_owned()),
+            [name] if Game::parse(name).is_some() => Game::parse(name).expect("Bug! Unknown game").streets(),
+            specs => specs.iter().map(|spec| Street::parse(spec)).collect::<Result<Vec<_>, _>>()?,
+        };
+        let cards: usize = streets.iter().map(|street| street.cards).sum();
+        let deck = self.game.variant().deck().len();
+        if cards > deck {
+            return Err(format!("Invalid streets, {} cards dealt from a deck of {}", cards, deck));
+        }
+        self.streets = streets;
+        self.dealt.clear();
+        Ok(())
+    }
+
+    // description, category and the five cards of the best hand after the first streets
+    fn best_hand(&self, streets: usize) -> String {
+        let (hole, board) = self.split(streets);
+        let hand = union(&hole, &board);
+        let variant = self.game.variant();
+        let candidates = match self.game {
+            Game::Holdem | Game::Stud => vec![hand.best_five()],
+            Game::ShortDeck if hand.len() <= 5 => vec![hand],
+            Game::ShortDeck => hand.combinations(5),
+            Game::Omaha => {
+                let boards = board.combinations(board.len().min(3));
+                hole.combinations(hole.len().min(2)).iter()
+                    .flat_map(|hole| boards.iter().map(move |board| union(hole, board)))
+                    .collect()
+            },
+        };
+        let (showdown, five) = candidates.into_iter()
+            .map(|hand| (variant.to_showdown_hand(&hand), hand))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .expect("Bug! No candidate hands");
+        format!("{} ({}): {}", showdown, showdown.order().name(), card_names(&five).join(" "))
+    }
+
+    // best hand, and outs and nut ranking of hold'em hands, after a street is dealt
+    fn report(&self) {
+        println!("Best hand: {}", self.best_hand(self.dealt.len()));
+
+        let (hole, board) = self.split(self.dealt.len());
+        if self.game != Game::Holdem || hole.len() != 2 {
+            return;
+        }
+        if board.len() == 3 || board.len() == 4 {
+            match poker::outs(&hole, &board, &self.dead, None) {
+                Ok(outs) => println!("{}", outs),
+                Err(e) => println!("{}", e.msg()),
+            }
+        }
+        if !board.is_empty() {
+            match poker::nut_ranking(&hole, &board) {
+                Ok(ranking) => println!("{}", ranking),
+                Err(e) => println!("{}", e.msg()),
+            }
+        }
+    }
+
+    fn show(&self) {
+        let streets: Vec<String> = self.streets.iter().map(|street| street.to_string()).collect();
+        println!("Variant: {}", self.game.name());
+        println!("Streets: {}", streets.join(", "));
+        self.show_dead();
+        if self.dealt.is_empty() {
+            println!("No cards dealt");
+        } else {
+            self.print_summary();
+        }
+    }
+
+    fn show_dead(&self) {
+        if self.dead.is_empty() {
+            println!("Dead cards: none");
+        } else {
+            println!("Dead cards: {}", card_names(&self.dead).join(" "));
+        }
+    }
+
+    // cards dealt and best hand after each street
+    fn print_summary(&self) {
+        if self.dealt.is_empty() {
+            return;
+        }
+        let cards: Vec<String> = self.dealt.iter().map(|cards| card_names(cards).join(" ")).collect();
+        let name_width = self.streets.iter().map(|street| street.name.len()).max().unwrap_or(0).max(8);
+        let cards_width = cards.iter().map(String::len).max().unwrap_or(0).max(9);
+        println!("\nSummary:");
+        for (i, (street, cards)) in self.streets.iter().zip(&cards).enumerate() {
+            println!("  {:<name_width$} {:<cards_width$} {}", street.name, cards, self.best_hand(i + 1));
+        }
+    }
+}
+
+fn union(a: &poker::BuildingHand, b: &poker::BuildingHand) -> poker::BuildingHand {
+    poker::BuildingHand::from_bits(a.bits() | b.bits())
+}
+
+fn build_hand() {
+    println!("\nPoker hand builder! Enter help for commands.");
+
+    let mut builder = Builder::new(Game::Holdem);
+    loop {
+        match builder.next_street() {
+            Some(street) => println!("\nAdd {} {} card(s):", street.cards, street.name),
+            None => println!("\nHand complete, undo, reset or quit:"),
+        }
+        let mut input = String::new();
+        let read = io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read input");
+        if read == 0 {
             break;
         }
+
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let command = words.first().map(|word| word.to_lowercase());
+        let result = match command.as_deref() {
+            Some("done") | Some("exit") | Some("quit") => break,
+            Some("help") => {
+                println!("{}", COMMANDS);
+                Ok(())
+            },
+            Some("undo") => match builder.dealt.pop() {
+                Some(cards) => {
+                    let street = &builder.streets[builder.dealt.len()];
+                    println!("Took back the {}: {}", street.name, card_names(&cards).join(" "));
+                    Ok(())
+                },
+                None => Err("Nothing to undo".to_owned()),
+            },
+            Some("reset") => {
+                builder.dealt.clear();
+                builder.dead = poker::BuildingHand::new();
+                println!("New hand");
+                Ok(())
+            },
+            Some("show") => {
+                builder.show();
+                Ok(())
+            },
+            Some("dead") => builder.set_dead(&words[1..].join(" ")).map(|()| builder.show_dead()),
+            Some("variant") => match words.get(1).and_then(|name| Game::parse(name)) {
+                Some(game) => {
+                    builder = Builder::new(game);
+                    println!("Playing {}, new hand", game.name());
+                    Ok(())
+                },
+                None => Err(format!("Variant {}, holdem, shortdeck, omaha or stud required", builder.game.name())),
+            },
+            Some("streets") => builder.set_streets(&words[1..]).map(|()| {
+                let streets: Vec<String> = builder.streets.iter().map(|street| street.to_string()).collect();
+                println!("Streets: {}, new hand", streets.join(", "));
+            }),
+            _ => builder.deal(&input).map(|()| {
+                builder.report();
+                if builder.next_street().is_none() {
+                    builder.print_summary();
+                }
+            }),
+        };
+        if let Err(msg) = result {
+            println!("{}", msg);
+        }
     }
 
+    if builder.next_street().is_some() {
+        builder.print_summary();
+    }
     println!();
 }
@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::{check_disjoint, BuildingHand, Error, ShowdownHand};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NutRanking {
+    pub ahead: usize,    // opponent holdings that beat the hand
+    pub tied: usize,
+    pub behind: usize,   // opponent holdings the hand beats
+    pub nut_rank: usize, // 1 for the nuts, 2 for the second nuts, ...
+}
+
+impl NutRanking {
+    pub fn is_nuts(&self) -> bool {
+        self.nut_rank == 1
+    }
+
+    pub fn holdings(&self) -> usize {
+        self.ahead + self.tied + self.behind
+    }
+}
+
+/// Where the player's hand ranks among every two card holding an opponent can have.
+///
+/// Opponent holdings never contain the player's hole cards, so the nut rank accounts for card
+/// removal, e.g. holding the ace of the flush suit can make the second best flush the nuts.
+pub fn nut_ranking(hole: &BuildingHand, board: &BuildingHand) -> Result<NutRanking, Error> {
+    if hole.len() != 2 {
+        return Err(Error::of(format!("Invalid hole cards, 2 required, {} given", hole.len())));
+    }
+    check_board(board)?;
+    check_disjoint(hole.0, board.0)?;
+
+    let showdown = BuildingHand(hole.0 | board.0).to_showdown_hand();
+    let mut ranking = NutRanking { ahead: 0, tied: 0, behind: 0, nut_rank: 1 };
+
+    for (hand, holdings) in groups(board, hole) {
+        match hand.cmp(&showdown) {
+            Ordering::Greater => {
+                ranking.ahead += holdings.len();
+                ranking.nut_rank += 1;
+            },
+            Ordering::Equal => ranking.tied += holdings.len(),
+            Ordering::Less => ranking.behind += holdings.len(),
+        }
+    }
+
+    Ok(ranking)
+}
+
+/// The n best hands on the board, best first, each with every holding that makes it.
+/// Dead cards can't be part of a holding.
+pub fn nut_hands(board: &BuildingHand, dead: &BuildingHand, n: usize) -> Result<Vec<(ShowdownHand, Vec<BuildingHand>)>, Error> {
+    check_board(board)?;
+    check_disjoint(board.0, dead.0)?;
+
+    let mut groups = groups(board, dead);
+    groups.truncate(n);
+    Ok(groups)
+}
+
+fn check_board(board: &BuildingHand) -> Result<(), Error> {
+    if board.len() < 3 || board.len() > 5 {
+        return Err(Error::of(format!("Invalid board, 3 to 5 cards required, {} given", board.len())));
+    }
+    Ok(())
+}
+
+// every holding of unseen cards grouped by the hand it makes, best first
+fn groups(board: &BuildingHand, known: &BuildingHand) -> Vec<(ShowdownHand, Vec<BuildingHand>)> {
+    let unseen = BuildingHand(0x000fffffffffffff & !board.0 & !known.0);
+    let mut hands: Vec<(ShowdownHand, BuildingHand)> = unseen.combinations(2)
+        .into_iter()
+        .map(|hole| (BuildingHand(hole.0 | board.0).to_showdown_hand(), hole))
+        .collect();
+    hands.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    let mut groups: Vec<(ShowdownHand, Vec<BuildingHand>)> = Vec::new();
+    for (hand, hole) in hands {
+        match groups.last_mut() {
+            Some((last, holdings)) if *last == hand => holdings.push(hole),
+            _ => groups.push((hand, vec![hole])),
+        }
+    }
+    groups
+}
+
+impl fmt::Display for NutRanking {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nuts = match self.nut_rank {
+            1 => "The nuts".to_owned(),
+            2 => "Second nuts".to_owned(),
+            3 => "Third nuts".to_owned(),
+            n => {
+                let suffix = match (n % 10, n % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{}{} nuts", n, suffix)
+            },
+        };
+        write!(f, "{}: {} holdings ahead, {} tied, {} behind", nuts, self.ahead, self.tied, self.behind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, ErrorKind, Order, Rank};
+
+    #[test]
+    fn nut_flush() {
+        let board = hand!("Ks 9s 4s 2d 7c");
+        let ranking = nut_ranking(&hand!("As 3s"), &board).unwrap();
+        assert!(ranking.is_nuts());
+        assert_eq!(0, ranking.ahead);
+        assert_eq!(0, ranking.tied);
+        assert_eq!(45 * 44 / 2, ranking.holdings());
+        assert_eq!("The nuts: 0 holdings ahead, 0 tied, 990 behind", ranking.to_string());
+    }
+
+    #[test]
+    fn second_nuts_and_card_removal() {
+        let board = hand!("Kd Kc 7h 2s 9d");
+
+        // kings full of nines is ahead, the other king with a seven ties
+        let ranking = nut_ranking(&hand!("Ks 7s"), &board).unwrap();
+        assert_eq!(2, ranking.nut_rank);
+        assert_eq!(3, ranking.ahead);
+        assert_eq!(2, ranking.tied);
+        assert_eq!("Second nuts: 3 holdings ahead, 2 tied, 985 behind", ranking.to_string());
+
+        // holding the last king makes kings full the nuts
+        let ranking = nut_ranking(&hand!("Ks 9s"), &board).unwrap();
+        assert!(ranking.is_nuts());
+    }
+
+    #[test]
+    fn ties_on_the_board() {
+        // a royal flush on the board, everybody chops
+        let board = hand!("As Ks Qs Js Ts");
+        let ranking = nut_ranking(&hand!("2d 3c"), &board).unwrap();
+        assert!(ranking.is_nuts());
+        assert_eq!(ranking.holdings(), ranking.tied);
+
+        let ranking = NutRanking { ahead: 60, tied: 0, behind: 0, nut_rank: 52 };
+        assert_eq!("52nd nuts: 60 holdings ahead, 0 tied, 0 behind", ranking.to_string());
+        assert_eq!("11th nuts", NutRanking { nut_rank: 11, ..ranking }.to_string().split(':').next().unwrap());
+    }
+
+    #[test]
+    fn top_nut_hands() {
+        let board = hand!("Kd 7c 2h");
+        let nuts = nut_hands(&board, &BuildingHand::new(), 3).unwrap();
+        assert_eq!(3, nuts.len());
+
+        // trip kings, trip sevens, trip deuces, three holdings each
+        let ranks: Vec<u8> = nuts.iter().map(|(hand, _)| hand.ranks()[0]).collect();
+        assert_eq!(vec![Rank::_K as u8, Rank::_7 as u8, Rank::_2 as u8], ranks);
+        assert!(nuts.iter().all(|(hand, holdings)| hand.order() == Order::Trip && holdings.len() == 3));
+        assert!(nuts[0].1.contains(&hand!("Kh Ks")));
+
+        // with two kings dead nobody can have a set of kings
+        let nuts = nut_hands(&board, &hand!("Kh Ks"), 1).unwrap();
+        assert_eq!(Rank::_7 as u8, nuts[0].0.ranks()[0]);
+
+        assert!(nut_hands(&hand!("Kd"), &BuildingHand::new(), 1).is_err());
+        assert_eq!(ErrorKind::DuplicateCard, nut_ranking(&hand!("Kd 7c"), &board).unwrap_err().kind);
+        assert_eq!(ErrorKind::DuplicateCard, nut_hands(&board, &hand!("Kd"), 1).unwrap_err().kind);
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt;
+
+use crate::{check_disjoint, BuildingHand, Card, Error, Order, CARDS};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Outs {
+    pub groups: Vec<(Order, Vec<Card>)>, // what the cards make, best first
+}
+
+impl Outs {
+    pub fn len(&self) -> usize {
+        self.groups.iter().map(|(_, cards)| cards.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn cards(&self) -> Vec<Card> {
+        self.groups.iter().flat_map(|(_, cards)| cards.iter().cloned()).collect()
+    }
+}
+
+/// Every unseen card that improves the player's hand class beyond what the board makes on its own,
+/// not counting cards that just pair the board, or that puts the player ahead of the opponent's
+/// hole cards when those are given and the player is behind or tied without it.
+pub fn outs(hole: &BuildingHand, board: &BuildingHand, dead: &BuildingHand, opponent: Option<&BuildingHand>) -> Result<Outs, Error> {
+    let opponent_cards = opponent.map(|hand| hand.0).unwrap_or(0);
+    let known = [hole.0, board.0, dead.0, opponent_cards];
+    for (i, a) in known.iter().enumerate() {
+        for b in &known[i + 1..] {
+            check_disjoint(*a, *b)?;
+        }
+    }
+
+    // every card is added to and then taken from the same hands
+    let mut player = BuildingHand(hole.0 | board.0).to_incremental_hand();
+    let mut other = match opponent {
+        Some(opponent) => BuildingHand(opponent.0 | board.0).to_incremental_hand(),
+        None => board.to_incremental_hand(),
+    };
+    let current = player.to_realized_hand().order();
+    let ahead = opponent.is_some() && player.to_showdown_hand() > other.to_showdown_hand();
+    let mut groups: Vec<(Order, Vec<Card>)> = Vec::new();
+
+    for card in CARDS.iter().filter(|&card| known.iter().all(|cards| cards & (card.clone() as u64) == 0)) {
+        player.add_card(card);
+        other.add_card(card);
+        let realized = player.to_realized_hand();
+
+        // pairing the board helps everyone, so the hole cards must play, and a card that pairs the
+        // board without pairing the hole cards only helps when it fills up a full house or better
+        let rank = 0xf << (4 * (card.rank() as u64 - 1));
+        let pairs_board = board.0 & rank != 0 && hole.0 & rank == 0;
+        let is_out = match opponent {
+            Some(_) => !ahead && realized.to_showdown_hand() > other.to_showdown_hand(),
+            None => realized.order() > current
+                && realized.order() > other.to_realized_hand().order()
+                && (!pairs_board || realized.order() > Order::Trip),
+        };
+        player.remove_card(card);
+        other.remove_card(card);
+        if !is_out {
+            continue;
+        }
+
+        let order = realized.order();
+        match groups.iter_mut().find(|(group, _)| *group == order) {
+            Some((_, cards)) => cards.push(card.clone()),
+            None => groups.push((order, vec![card.clone()])),
+        }
+    }
+
+    groups.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(Outs { groups })
+}
+
+impl fmt::Display for Outs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No outs");
+        }
+
+        let lines: Vec<String> = self.groups.iter()
+            .map(|(order, cards)| {
+                let article = match order {
+                    Order::Twop | Order::Trip | Order::Quad | Order::Fivk => "",
+                    _ => "a ",
+                };
+                let cards: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+                format!("{} out{} to {}{}: {}", cards.len(), if cards.len() == 1 { "" } else { "s" }, article, order.name(), cards.join(" "))
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, ErrorKind};
+
+    #[test]
+    fn flush_draw_outs() {
+        let hole = hand!("As Js");
+        let board = hand!("Ks 7s 2h");
+        let outs = outs(&hole, &board, &BuildingHand::new(), None).unwrap();
+
+        // nine spades, plus three aces, three jacks for a pair
+        assert_eq!(Order::Flsh, outs.groups[0].0);
+        assert_eq!(9, outs.groups[0].1.len());
+        assert_eq!(Order::Pair, outs.groups[1].0);
+        assert_eq!(6, outs.groups[1].1.len());
+        assert_eq!(15, outs.len());
+        assert!(outs.to_string().starts_with("9 outs to a flush: 2s 3s 4s 5s 6s 8s 9s Ts Qs\n6 outs to a pair:"));
+    }
+
+    #[test]
+    fn pairing_the_board_is_not_an_out() {
+        let hole = hand!("Ah Kh");
+        let board = hand!("As 7c 2d");
+        let aces = outs(&hole, &board, &BuildingHand::new(), None).unwrap();
+        assert_eq!(vec![
+            (Order::Trip, vec![Card::_Ad, Card::_Ac]),
+            (Order::Twop, vec![Card::_Kd, Card::_Kc, Card::_Ks]),
+        ], aces.groups);
+
+        // unless it fills up a set
+        let hole = hand!("9h 9s");
+        let board = hand!("9c 7c 2d");
+        let set = outs(&hole, &board, &BuildingHand::new(), None).unwrap();
+        assert_eq!(Order::Quad, set.groups[0].0);
+        assert_eq!((Order::Boat, 6), (set.groups[1].0.clone(), set.groups[1].1.len()));
+    }
+
+    #[test]
+    fn dead_cards_are_not_outs() {
+        let hole = hand!("As Js");
+        let board = hand!("Ks 7s 2h 2c");
+        let dead = hand!("3s 4s");
+        let outs = outs(&hole, &board, &dead, None).unwrap();
+        assert_eq!(Order::Flsh, outs.groups[0].0);
+        assert_eq!(7, outs.groups[0].1.len());
+        assert!(!outs.cards().contains(&Card::_3s));
+    }
+
+    #[test]
+    fn outs_against_opponent() {
+        let hole = hand!("9h 8h");
+        let opponent = hand!("Ac Ad");
+        let board = hand!("7c 6d 2s Kh");
+        let outs = outs(&hole, &board, &BuildingHand::new(), Some(&opponent)).unwrap();
+
+        // any ten or five makes the straight
+        assert_eq!(vec![(Order::Strt, vec![Card::_5d, Card::_5c, Card::_5h, Card::_5s, Card::_Td, Card::_Tc, Card::_Th, Card::_Ts])], outs.groups);
+        assert_eq!("8 outs to a straight: 5d 5c 5h 5s Td Tc Th Ts", outs.to_string());
+    }
+
+    #[test]
+    fn no_outs_when_already_ahead() {
+        let hole = hand!("Ac Ad");
+        let opponent = hand!("Kc Kd");
+        let board = hand!("7h 6s 2c");
+        let outs = outs(&hole, &board, &BuildingHand::new(), Some(&opponent)).unwrap();
+        assert!(outs.is_empty());
+        assert_eq!("No outs", outs.to_string());
+    }
+
+    #[test]
+    fn overlapping_cards() {
+        let hole = hand!("9h 8h");
+        let board = hand!("7c 6d 2s");
+        let duplicate = outs(&hole, &board, &hand!("9h"), None).unwrap_err();
+        assert_eq!((ErrorKind::DuplicateCard, "Duplicate card: 9h"), (duplicate.kind, duplicate.msg()));
+        assert_eq!("No outs", Outs { groups: vec![] }.to_string());
+    }
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+use crate::{BuildingHand, Card, Order, RealizedHand, ShortDeckRules, ShowdownHand, Variant};
+
+/* Serialized forms, every one of which deserializes back to the same value:
+ * - Card: "As", suit masks aren't cards and don't serialize
+ * - BuildingHand: cards like "As Ks 2d", a "*" per joker, in human readable formats such as JSON,
+ *   the raw u64 in binary formats, and either is accepted in human readable formats
+ * - RealizedHand, ShowdownHand: { bits, category }, the raw bits and the name of the hand class,
+ *   which has to agree with the bits, and the bits have to be those of a hand that can be dealt
+ */
+
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if (self.clone() as u64).count_ones() != 1 {
+            return Err(ser::Error::custom(format!("Suit mask {} is not a card", self)));
+        }
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Card, D::Error> {
+        deserializer.deserialize_str(CardVisitor)
+    }
+}
+
+struct CardVisitor;
+
+impl<'de> Visitor<'de> for CardVisitor {
+    type Value = Card;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a card like \"As\"")
+    }
+
+    fn visit_str<E: de::Error>(self, card: &str) -> Result<Card, E> {
+        card.parse().map_err(|_| E::invalid_value(de::Unexpected::Str(card), &self))
+    }
+}
+
+impl Serialize for BuildingHand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_u64(self.0);
+        }
+
+        let cards: Vec<String> = self.cards().iter()
+            .map(|card| card.to_string())
+            .chain((0..self.jokers()).map(|_| "*".to_owned()))
+            .collect();
+        serializer.serialize_str(&cards.join(" "))
+    }
+}
+
+impl<'de> Deserialize<'de> for BuildingHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BuildingHand, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BuildingHandVisitor)
+        } else {
+            deserializer.deserialize_u64(BuildingHandVisitor)
+        }
+    }
+}
+
+struct BuildingHandVisitor;
+
+impl<'de> Visitor<'de> for BuildingHandVisitor {
+    type Value = BuildingHand;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cards like \"As Ks 2d\" or the raw bits of a hand")
+    }
+
+    fn visit_u64<E: de::Error>(self, bits: u64) -> Result<BuildingHand, E> {
+        // only card and joker bits
+        if bits >> 56 != 0 {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(bits), &self));
+        }
+        Ok(BuildingHand(bits))
+    }
+
+    fn visit_str<E: de::Error>(self, cards: &str) -> Result<BuildingHand, E> {
+        let mut hand = BuildingHand::new();
+        for card in cards.split_whitespace() {
+            let error = if card == "*" {
+                hand.add_joker()
+            } else {
+                match card.parse::<Card>() {
+                    Ok(card) => hand.add_card(&card, true),
+                    Err(_) => return Err(E::invalid_value(de::Unexpected::Str(card), &CardVisitor)),
+                }
+            };
+            if let Some(e) = error {
+                return Err(E::custom(e.msg()));
+            }
+        }
+        Ok(hand)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Classified<B> {
+    bits: B,
+    category: String,
+}
+
+impl Serialize for RealizedHand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Classified { bits: self.0, category: self.order().name().to_owned() }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RealizedHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<RealizedHand, D::Error> {
+        let hand = Classified::<u64>::deserialize(deserializer)?;
+        check_category((hand.bits >> 60) as u8, &hand.category)?;
+        match realized_hand(hand.bits) {
+            Some(realized) if realized.0 == hand.bits => Ok(realized),
+            _ => Err(de::Error::custom(format!("Hand bits don't match the cards: {:#x}", hand.bits))),
+        }
+    }
+}
+
+impl Serialize for ShowdownHand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Classified { bits: self.0, category: self.order().name().to_owned() }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ShowdownHand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ShowdownHand, D::Error> {
+        let hand = Classified::<u32>::deserialize(deserializer)?;
+        check_category((hand.bits >> 28) as u8, &hand.category)?;
+        match showdown_hand(hand.bits) {
+            Some(showdown) if showdown.0 == hand.bits => Ok(showdown),
+            _ => Err(de::Error::custom(format!("Hand bits don't match any hand: {:#x}", hand.bits))),
+        }
+    }
+}
+
+// the hand the card bits evaluate to, where wild cards make five of a kind out of the naturals alone
+fn realized_hand(bits: u64) -> Option<RealizedHand> {
+    let cards = BuildingHand(bits & 0x000fffffffffffff);
+    if bits >> 60 != Order::Fivk as u64 {
+        return Some(cards.to_realized_hand());
+    }
+
+    let rank = (bits >> 56) & 0xf;
+    let naturals = (1..14).contains(&rank) && cards.0 != 0 && cards.0 & !(0xf << (4 * (rank - 1))) == 0;
+    Some(cards.new_realized_hand(Order::Fivk, rank, 0)).filter(|_| naturals)
+}
+
+// the hand cards of the given ranks and suit evaluate to under the given ranking
+fn showdown_hand(bits: u32) -> Option<ShowdownHand> {
+    let hand = ShowdownHand(bits);
+    let ranks: Vec<u32> = hand.ranks().iter().filter(|&&rank| rank != 0).map(|&rank| rank as u32).collect();
+    if ranks.iter().any(|&rank| rank > 13) {
+        return None;
+    }
+
+    let order = hand.order();
+    if order == Order::Fivk {
+        return ranks.first().map(|&rank| ShowdownHand::new(Order::Fivk, 0, &[rank; 5]));
+    }
+
+    // one suit for flushes, otherwise suits in turn, which never repeat a card of the same rank
+    let cards = ranks.iter().enumerate().fold(0, |cards, (i, &rank)| {
+        let suit = if order == Order::Flsh || order == Order::Stfl { hand.suit_bits() } else { i as u32 % 4 };
+        cards | 1 << (4 * (rank - 1) + suit)
+    });
+    let cards = BuildingHand(cards);
+    match (bits >> 24) & 0b11 {
+        0 => Some(cards.to_showdown_hand()),
+        ranking @ (1 | 2) => Some(Variant::ShortDeck(ShortDeckRules { trips_beat_straight: ranking == 2 }).to_showdown_hand(&cards)),
+        _ => None,
+    }
+}
+
+// the order bits have to be a known hand class with the given name
+fn check_category<E: de::Error>(order: u8, category: &str) -> Result<(), E> {
+    if order > Order::Fivk as u8 {
+        return Err(E::custom(format!("Invalid hand class bits: {}", order)));
+    }
+    let order = Order::from_bits(order);
+    if order.name() != category {
+        return Err(E::custom(format!("Category {} doesn't match the bits, {}", category, order.name())));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{WildRules, CARDS};
+    use rand::seq::SliceRandom;
+    use rand::{rngs::StdRng, SeedableRng};
+    use serde::de::IntoDeserializer;
+    use serde_test::{assert_de_tokens_error, assert_ser_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
+
+    #[test]
+    fn cards() {
+        assert_tokens(&Card::_As, &[Token::Str("As")]);
+        assert_tokens(&Card::_Td, &[Token::Str("Td")]);
+        assert_de_tokens_error::<Card>(&[Token::Str("Ax")], "invalid value: string \"Ax\", expected a card like \"As\"");
+        for card in CARDS.iter() {
+            let string: de::value::StringDeserializer<de::value::Error> = card.to_string().into_deserializer();
+            assert_eq!(Ok(card.clone()), Card::deserialize(string));
+        }
+    }
+
+    #[test]
+    fn building_hands() {
+        let mut hand = crate::hand!("As Ks 2d");
+        hand.add_joker();
+        let bits = hand.0;
+
+        assert_tokens(&hand.clone().readable(), &[Token::Str("2d Ks As *")]);
+        assert_tokens(&hand.clone().compact(), &[Token::U64(bits)]);
+
+        // human readable formats take the raw bits too
+        serde_test::assert_de_tokens(&hand.clone().readable(), &[Token::U64(bits)]);
+        assert_de_tokens_error::<Readable<BuildingHand>>(&[Token::Str("As As")], "Duplicate card: As");
+        assert_de_tokens_error::<Compact<BuildingHand>>(
+            &[Token::U64(1 << 60)],
+            "invalid value: integer `1152921504606846976`, expected cards like \"As Ks 2d\" or the raw bits of a hand",
+        );
+    }
+
+    #[test]
+    fn classified_hands() {
+        let hand = crate::hand!("As Ks Qs Js 9s 2d");
+        let realized = hand.to_realized_hand();
+        let showdown = hand.to_showdown_hand();
+        let tokens = |bits: Token| vec![
+            Token::Struct { name: "Classified", len: 2 },
+            Token::Str("bits"),
+            bits,
+            Token::Str("category"),
+            Token::Str("flush"),
+            Token::StructEnd,
+        ];
+
+        assert_tokens(&realized, &tokens(Token::U64(realized.0)));
+        assert_tokens(&showdown, &tokens(Token::U32(showdown.0)));
+        assert_de_tokens_error::<ShowdownHand>(
+            &[
+                Token::Struct { name: "Classified", len: 2 },
+                Token::Str("bits"),
+                Token::U32(showdown.0),
+                Token::Str("category"),
+                Token::Str("straight"),
+                Token::StructEnd,
+            ],
+            "Category straight doesn't match the bits, flush",
+        );
+    }
+
+    #[test]
+    fn classified_hands_round_trip() {
+        let tokens = |bits: Token, category: &'static str| vec![
+            Token::Struct { name: "Classified", len: 2 },
+            Token::Str("bits"),
+            bits,
+            Token::Str("category"),
+            Token::Str(category),
+            Token::StructEnd,
+        ];
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut deck = CARDS.to_vec();
+        let short_deck = Variant::ShortDeck(ShortDeckRules { trips_beat_straight: true });
+        for n in 0..=9 {
+            for _ in 0..200 {
+                deck.shuffle(&mut rng);
+                let hand: BuildingHand = deck[..n].iter().fold(BuildingHand::new(), |hand, card| hand.with_card(card.clone()));
+                let realized = hand.to_realized_hand();
+                assert_tokens(&realized, &tokens(Token::U64(realized.0), realized.order().name()));
+                for showdown in [hand.to_showdown_hand(), short_deck.to_showdown_hand(&hand)].iter() {
+                    assert_tokens(showdown, &tokens(Token::U32(showdown.0), showdown.order().name()));
+                }
+            }
+        }
+
+        let mut wild = crate::hand!("9d 9c 9h Kd");
+        wild.add_joker();
+        wild.add_joker();
+        let realized = wild.to_wild_realized_hand(&WildRules::default());
+        assert_eq!(Order::Fivk, realized.order());
+        assert_tokens(&realized, &tokens(Token::U64(realized.0), "five of a kind"));
+        let showdown = realized.to_showdown_hand();
+        assert_tokens(&showdown, &tokens(Token::U32(showdown.0), "five of a kind"));
+    }
+
+    #[test]
+    fn inconsistent_hands() {
+        let tokens = |bits: Token, category: &'static str| vec![
+            Token::Struct { name: "Classified", len: 2 },
+            Token::Str("bits"),
+            bits,
+            Token::Str("category"),
+            Token::Str(category),
+            Token::StructEnd,
+        ];
+
+        // a pair of kings claimed for a pair of aces
+        let realized = crate::hand!("Ad Ac 7h 4s 2d").to_realized_hand();
+        let kings = realized.0 & !(0xff << 52) | 12 << 56 | 13 << 52;
+        assert_de_tokens_error::<RealizedHand>(&tokens(Token::U64(kings), "pair"), &format!("Hand bits don't match the cards: {:#x}", kings));
+        let five_kings = (Order::Fivk as u64) << 60 | 12 << 56 | 0xf << 48;
+        assert_de_tokens_error::<RealizedHand>(&tokens(Token::U64(five_kings), "five of a kind"), &format!("Hand bits don't match the cards: {:#x}", five_kings));
+
+        let showdown = crate::hand!("Ad Ac 7h 4s 2d").to_showdown_hand();
+        let invalid = [
+            showdown.0 | 0xf << 16,     // no rank 15
+            showdown.0 | 1 << 26,       // no suit for a pair
+            showdown.0 | 3 << 24,       // no such ranking
+            showdown.0 | 1 << 20,       // TBD bits
+            showdown.0 & !0xff | 0x23,  // kickers out of order
+            (Order::Flsh as u32) << 28 | 0xcccc0, // a flush of aces
+        ];
+        for &bits in invalid.iter() {
+            let category = ShowdownHand(bits).order().name();
+            assert_de_tokens_error::<ShowdownHand>(&tokens(Token::U32(bits), category), &format!("Hand bits don't match any hand: {:#x}", bits));
+        }
+    }
+
+    #[test]
+    fn suit_masks_are_not_cards() {
+        assert_ser_tokens_error(&Card::_Md, &[], "Suit mask d is not a card");
+        assert_ser_tokens_error(&Card::_Ms, &[], "Suit mask s is not a card");
+    }
+}
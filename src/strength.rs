@@ -0,0 +1,182 @@
+use std::cmp::Ordering;
+
+use crate::{check_disjoint, BuildingHand, Error};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandStrength {
+    pub hs: f64,   // share of opponent holdings the hand beats right now, ties count half
+    pub ppot: f64, // chance of getting ahead by the river when behind or tied now
+    pub npot: f64, // chance of falling behind by the river when ahead or tied now
+    pub ehs: f64,  // hs * (1 - npot) + (1 - hs) * ppot
+    pub ehs2: f64, // mean squared hand strength over every river
+}
+
+/// Hand strength and potential of the hole cards on a flop, turn or river board.
+///
+/// The opponent holds any two unseen cards unless a range of weighted holdings is given. Holdings
+/// that share a card with the hole cards or the board are left out of the range. Potentials look
+/// ahead to the river, so both cards to come on the flop.
+pub fn hand_strength(hole: &BuildingHand, board: &BuildingHand, range: Option<&[(BuildingHand, f64)]>) -> Result<HandStrength, Error> {
+    if hole.len() != 2 {
+        return Err(Error::of(format!("Invalid hole cards, 2 required, {} given", hole.len())));
+    }
+    if board.len() < 3 || board.len() > 5 {
+        return Err(Error::of(format!("Invalid board, 3 to 5 cards required, {} given", board.len())));
+    }
+    check_disjoint(hole.0, board.0)?;
+
+    let known = hole.0 | board.0;
+    let opponents: Vec<(BuildingHand, f64)> = match range {
+        Some(range) => {
+            if let Some((holding, _)) = range.iter().find(|(holding, _)| holding.len() != 2) {
+                return Err(Error::of(format!("Invalid range, 2 cards required per holding, {} given", holding.len())));
+            }
+            range.iter().filter(|(holding, weight)| holding.0 & known == 0 && *weight > 0.0).cloned().collect()
+        },
+        None => BuildingHand(0x000fffffffffffff & !known).combinations(2).into_iter().map(|holding| (holding, 1.0)).collect(),
+    };
+    if opponents.is_empty() {
+        return Err(Error::of("Invalid range, no holding is possible with these cards".to_owned()));
+    }
+
+    let mine = BuildingHand(known).to_showdown_hand();
+    let now: Vec<usize> = opponents.iter()
+        .map(|(holding, _)| index(mine.cmp(&BuildingHand(holding.0 | board.0).to_showdown_hand())))
+        .collect();
+
+    // weighted counts of ahead/tied/behind now (rows) against the river (columns)
+    let mut hp = [[0f64; 3]; 3];
+    let (mut ehs2, mut total) = (0f64, 0f64);
+
+    for runout in BuildingHand(0x000fffffffffffff & !known).combinations(5 - board.len()) {
+        let river = board.0 | runout.0;
+        let mine = BuildingHand(hole.0 | river).to_showdown_hand();
+        let (mut strength, mut weight) = (0f64, 0f64);
+
+        for ((holding, w), &before) in opponents.iter().zip(&now) {
+            if holding.0 & runout.0 != 0 {
+                continue;
+            }
+            let after = index(mine.cmp(&BuildingHand(holding.0 | river).to_showdown_hand()));
+            hp[before][after] += w;
+            strength += w * score(after);
+            weight += w;
+        }
+
+        if weight > 0.0 {
+            ehs2 += weight * (strength / weight).powi(2);
+            total += weight;
+        }
+    }
+
+    let weights: Vec<f64> = (0..3).map(|i| opponents.iter().zip(&now).filter(|(_, &before)| before == i).map(|((_, w), _)| w).sum()).collect();
+    let hs = (weights[AHEAD] + weights[TIED] / 2.0) / weights.iter().sum::<f64>();
+
+    let row = |i: usize| hp[i].iter().sum::<f64>();
+    let ratio = |n: f64, d: f64| if d > 0.0 { n / d } else { 0.0 };
+    let ppot = ratio(hp[BEHIND][AHEAD] + hp[BEHIND][TIED] / 2.0 + hp[TIED][AHEAD] / 2.0, row(BEHIND) + row(TIED) / 2.0);
+    let npot = ratio(hp[AHEAD][BEHIND] + hp[TIED][BEHIND] / 2.0 + hp[AHEAD][TIED] / 2.0, row(AHEAD) + row(TIED) / 2.0);
+
+    Ok(HandStrength {
+        hs,
+        ppot,
+        npot,
+        ehs: hs * (1.0 - npot) + (1.0 - hs) * ppot,
+        ehs2: ratio(ehs2, total),
+    })
+}
+
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+fn index(ordering: Ordering) -> usize {
+    match ordering {
+        Ordering::Greater => AHEAD,
+        Ordering::Equal => TIED,
+        Ordering::Less => BEHIND,
+    }
+}
+
+fn score(index: usize) -> f64 {
+    match index {
+        AHEAD => 1.0,
+        TIED => 0.5,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hand, ErrorKind};
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!((expected - actual).abs() < 1e-9, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn river_has_no_potential() {
+        let board = hand!("Ks 9s 4s 2d 7c");
+        let nuts = hand_strength(&hand!("As 3s"), &board, None).unwrap();
+        assert_eq!(HandStrength { hs: 1.0, ppot: 0.0, npot: 0.0, ehs: 1.0, ehs2: 1.0 }, nuts);
+
+        // a royal flush on the board, everybody chops
+        let board = hand!("As Ks Qs Js Ts");
+        let chop = hand_strength(&hand!("2d 3c"), &board, None).unwrap();
+        assert_close(0.5, chop.hs);
+        assert_close(0.25, chop.ehs2);
+    }
+
+    #[test]
+    fn straight_draw_against_aces() {
+        let hole = hand!("9h 8h");
+        let board = hand!("7c 6d 2s Kh");
+        let aces = [(hand!("Ac Ad"), 1.0)];
+        let strength = hand_strength(&hole, &board, Some(&aces)).unwrap();
+
+        // eight of the 44 unseen cards make the straight
+        assert_close(0.0, strength.hs);
+        assert_close(8.0 / 44.0, strength.ppot);
+        assert_close(0.0, strength.npot);
+        assert_close(8.0 / 44.0, strength.ehs);
+        assert_close(8.0 / 44.0, strength.ehs2);
+    }
+
+    #[test]
+    fn weighted_range() {
+        let hole = hand!("9h 8h");
+        let board = hand!("7c 6d 2s Kh");
+        let range = [
+            (hand!("Ac Ad"), 3.0),
+            (hand!("5c 4c"), 1.0),
+            (hand!("9h 9d"), 5.0), // blocked by the hole cards
+        ];
+        let strength = hand_strength(&hole, &board, Some(&range)).unwrap();
+        assert_close(0.25, strength.hs);
+        assert!(strength.npot > 0.0);
+
+        assert!(hand_strength(&hole, &board, Some(&range[2..])).is_err());
+        assert!(hand_strength(&hole, &board, Some(&[(hand!("Ac"), 1.0)])).is_err());
+    }
+
+    #[test]
+    fn flop_potential() {
+        let hole = hand!("Ah Kh");
+        let board = hand!("Qh 7h 2c");
+        let range = [(hand!("Qc Qd"), 1.0)];
+        let strength = hand_strength(&hole, &board, Some(&range)).unwrap();
+
+        // behind a set with the nut flush draw
+        assert_close(0.0, strength.hs);
+        assert!(strength.ppot > 0.25 && strength.ppot < 0.35, "{}", strength.ppot);
+        assert_close(strength.ppot, strength.ehs);
+
+        let turn = hand!("Qh 7h 2c 3d");
+        let random = hand_strength(&hole, &turn, None).unwrap();
+        assert!(random.ehs > random.hs && random.ppot > random.npot);
+        assert!(random.ehs2 > 0.0 && random.ehs2 < 1.0);
+        assert!(hand_strength(&hole, &hand!("Qh"), None).is_err());
+        assert_eq!(ErrorKind::DuplicateCard, hand_strength(&hole, &hand!("Ah 7h 2c"), None).unwrap_err().kind);
+    }
+}
@@ -0,0 +1,187 @@
+use std::cmp::Ordering;
+
+use crate::{BuildingHand, Card, Error, ErrorKind, Lowball, Rank};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum StudGame {
+    SevenCard, // high hand wins, lowest upcard brings it in
+    Razz,      // ace-to-five low wins, highest upcard brings it in
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct StudHand {
+    down: BuildingHand,
+    up: BuildingHand,
+}
+
+impl StudHand {
+    pub fn new() -> StudHand {
+        StudHand { down: BuildingHand::new(), up: BuildingHand::new() }
+    }
+
+    pub fn add_down_card(&mut self, card: &Card) -> Option<Error> {
+        if self.up.contains(card) {
+            return Some(error!("Duplicate card", "{}", card).kind(ErrorKind::DuplicateCard));
+        }
+        self.down.add_card(card, true)
+    }
+
+    pub fn add_up_card(&mut self, card: &Card) -> Option<Error> {
+        if self.down.contains(card) {
+            return Some(error!("Duplicate card", "{}", card).kind(ErrorKind::DuplicateCard));
+        }
+        self.up.add_card(card, true)
+    }
+
+    pub fn down_cards(&self) -> &BuildingHand {
+        &self.down
+    }
+
+    /// The cards every player at the table can see.
+    pub fn up_cards(&self) -> &BuildingHand {
+        &self.up
+    }
+
+    pub fn to_building_hand(&self) -> BuildingHand {
+        BuildingHand(self.down.0 | self.up.0)
+    }
+
+    // the upcard that decides the bring-in, lowest for high games and highest for razz
+    fn door_card(&self, game: &StudGame) -> Option<Card> {
+        let cards = self.up.cards();
+        match game {
+            StudGame::SevenCard => cards.into_iter().min_by(|a, b| cmp_bring_in(a, b, game)),
+            StudGame::Razz => cards.into_iter().max_by(|a, b| cmp_bring_in(a, b, game)),
+        }
+    }
+
+    // visible hand strength on later streets, greater acts first
+    fn cmp_up_cards(&self, other: &StudHand, game: &StudGame) -> Ordering {
+        match game {
+            StudGame::SevenCard => self.up.to_showdown_hand().cmp(&other.up.to_showdown_hand()),
+            StudGame::Razz => self.up.to_low_hand(&Lowball::AceToFive).cmp(&other.up.to_low_hand(&Lowball::AceToFive)),
+        }
+    }
+}
+
+// ranks first, aces low in razz, then suits in Suit order
+fn cmp_bring_in(a: &Card, b: &Card, game: &StudGame) -> Ordering {
+    let rank = |card: &Card| match (game, card.rank()) {
+        (StudGame::Razz, Rank::_A) => 0,
+        (_, rank) => rank as u8,
+    };
+    rank(a).cmp(&rank(b)).then_with(|| a.suit().cmp(&b.suit()))
+}
+
+/// Seat of the player forced to bring in on third street, None if nobody shows an upcard.
+pub fn bring_in(players: &[StudHand], game: &StudGame) -> Option<usize> {
+    let door_cards = players.iter()
+        .enumerate()
+        .filter_map(|(seat, player)| player.door_card(game).map(|card| (seat, card)));
+
+    let bring_in = match game {
+        StudGame::SevenCard => door_cards.min_by(|(_, a), (_, b)| cmp_bring_in(a, b, game)),
+        StudGame::Razz => door_cards.max_by(|(_, a), (_, b)| cmp_bring_in(a, b, game)),
+    };
+    bring_in.map(|(seat, _)| seat)
+}
+
+/// Seat of the player with the best visible hand, who acts first from fourth street on.
+/// Ties go to the earliest seat.
+pub fn first_to_act(players: &[StudHand], game: &StudGame) -> Option<usize> {
+    players.iter()
+        .enumerate()
+        .filter(|(_, player)| !player.up.is_empty())
+        .fold(None, |best: Option<(usize, &StudHand)>, (seat, player)| match best {
+            Some((_, best_player)) if player.cmp_up_cards(best_player, game) != Ordering::Greater => best,
+            _ => Some((seat, player)),
+        })
+        .map(|(seat, _)| seat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(down: &[Card], up: &[Card]) -> StudHand {
+        let mut hand = StudHand::new();
+        for card in down {
+            hand.add_down_card(card);
+        }
+        for card in up {
+            hand.add_up_card(card);
+        }
+        hand
+    }
+
+    #[test]
+    fn duplicate_cards_across_up_and_down() {
+        let mut hand = player(&[Card::_As, Card::_Kd], &[]);
+        assert!(hand.add_up_card(&Card::_As).is_some());
+        assert_eq!(None, hand.add_up_card(&Card::_Ah));
+        assert!(hand.add_down_card(&Card::_Ah).is_some());
+        assert_eq!(3, hand.to_building_hand().len());
+    }
+
+    #[test]
+    fn bring_in_lowest_upcard() {
+        let players = [
+            player(&[Card::_As, Card::_Kd], &[Card::_9c]),
+            player(&[Card::_2s, Card::_2d], &[Card::_3h]),
+            player(&[Card::_Qs, Card::_Jd], &[Card::_3c]),
+            player(&[Card::_5s, Card::_6d], &[Card::_Ad]),
+        ];
+        // threes tie, clubs come before hearts
+        assert_eq!(Some(2), bring_in(&players, &StudGame::SevenCard));
+    }
+
+    #[test]
+    fn bring_in_highest_upcard_in_razz() {
+        let players = [
+            player(&[Card::_As, Card::_Kd], &[Card::_Kc]),
+            player(&[Card::_2s, Card::_2d], &[Card::_Ks]),
+            player(&[Card::_Qs, Card::_Jd], &[Card::_Qc]),
+            player(&[Card::_5s, Card::_6d], &[Card::_Ad]),
+        ];
+        assert_eq!(Some(1), bring_in(&players, &StudGame::Razz));
+        assert_eq!(None, bring_in(&[StudHand::new()], &StudGame::Razz));
+    }
+
+    #[test]
+    fn first_to_act_best_visible_hand() {
+        let players = [
+            player(&[Card::_As, Card::_Kd], &[Card::_Ac, Card::_Kh]),
+            player(&[Card::_2s, Card::_2d], &[Card::_3h, Card::_3c]),
+            player(&[Card::_Qs, Card::_Jd], &[Card::_Qc, Card::_Jc]),
+        ];
+        assert_eq!(Some(1), first_to_act(&players, &StudGame::SevenCard));
+        assert_eq!(Some(0), first_to_act(&[players[0].clone(), players[2].clone()], &StudGame::SevenCard));
+
+        // same visible pair, the kicker decides
+        let players = [
+            player(&[Card::_As, Card::_Kd], &[Card::_8c, Card::_8h, Card::_4d]),
+            player(&[Card::_2s, Card::_2d], &[Card::_8d, Card::_8s, Card::_Kh]),
+        ];
+        assert_eq!(Some(1), first_to_act(&players, &StudGame::SevenCard));
+    }
+
+    #[test]
+    fn first_to_act_ties_go_to_earliest_seat() {
+        let players = [
+            player(&[Card::_As, Card::_Kd], &[Card::_9c, Card::_5h]),
+            player(&[Card::_2s, Card::_2d], &[Card::_9d, Card::_5s]),
+        ];
+        assert_eq!(Some(0), first_to_act(&players, &StudGame::SevenCard));
+        assert_eq!(Some(0), first_to_act(&players, &StudGame::Razz));
+    }
+
+    #[test]
+    fn first_to_act_best_low_in_razz() {
+        let players = [
+            player(&[Card::_As, Card::_Kd], &[Card::_7c, Card::_2h]),
+            player(&[Card::_2s, Card::_2d], &[Card::_6d, Card::_5s]),
+            player(&[Card::_Qs, Card::_Jd], &[Card::_Ac, Card::_Ad]),
+        ];
+        assert_eq!(Some(1), first_to_act(&players, &StudGame::Razz));
+    }
+}
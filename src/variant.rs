@@ -0,0 +1,120 @@
+use crate::{ranked_order, BuildingHand, Deck, Order, Rank, ShowdownHand};
+
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct ShortDeckRules {
+    pub trips_beat_straight: bool, // house rule, some rooms rank trips above straights
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Variant {
+    Holdem,
+    ShortDeck(ShortDeckRules),
+}
+
+impl Variant {
+    pub fn deck(&self) -> Deck {
+        match self {
+            Variant::Holdem => Deck::new(),
+            Variant::ShortDeck(_) => Deck::short(),
+        }
+    }
+
+    /// Position of a hand class in this variant's ranking, higher is better.
+    pub fn order_rank(&self, order: &Order) -> u8 {
+        ranked_order(self.ranking(), order.clone() as u32) as u8
+    }
+
+    // ranking bits of the variant's showdown hands
+    fn ranking(&self) -> u32 {
+        match self {
+            Variant::Holdem => 0,
+            Variant::ShortDeck(rules) if rules.trips_beat_straight => 2,
+            Variant::ShortDeck(_) => 1,
+        }
+    }
+
+    /// Best hand under this variant's rules, out of every five card combination.
+    pub fn to_showdown_hand(&self, hand: &BuildingHand) -> ShowdownHand {
+        match self {
+            Variant::Holdem => hand.to_showdown_hand(),
+            Variant::ShortDeck(_) if hand.len() <= 5 => short_deck_showdown_hand(hand).with_ranking(self.ranking()),
+            Variant::ShortDeck(_) => hand.combinations(5)
+                .iter()
+                .map(|hand| short_deck_showdown_hand(hand).with_ranking(self.ranking()))
+                .max()
+                .expect("Bug! No combinations of hand"),
+        }
+    }
+}
+
+// with twos through fives gone, the ace plays low under the six instead
+fn short_deck_showdown_hand(hand: &BuildingHand) -> ShowdownHand {
+    let showdown = hand.to_showdown_hand();
+    let low_straight = [Rank::_9 as u32, Rank::_8 as u32, Rank::_7 as u32, Rank::_6 as u32, Rank::_A as u32];
+
+    let mut ranks = showdown.ranks();
+    ranks.sort_unstable();
+    if ranks != [Rank::_6 as u8, Rank::_7 as u8, Rank::_8 as u8, Rank::_9 as u8, Rank::_A as u8] {
+        return showdown;
+    }
+
+    match showdown.order() {
+        Order::High => ShowdownHand::new(Order::Strt, 0, &low_straight),
+        Order::Flsh => ShowdownHand::new(Order::Stfl, showdown.suit_bits(), &low_straight),
+        _ => showdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand;
+
+    #[test]
+    fn short_deck_ace_plays_under_six() {
+        let variant = Variant::ShortDeck(ShortDeckRules::default());
+        let low_straight = variant.to_showdown_hand(&hand!("As 6d 7c 8h 9s Kd Kc"));
+        assert_eq!(Order::Strt, low_straight.order());
+        assert_eq!([Rank::_9 as u8, Rank::_8 as u8, Rank::_7 as u8, Rank::_6 as u8, Rank::_A as u8], low_straight.ranks());
+
+        let next_straight = variant.to_showdown_hand(&hand!("Ts 6d 7c 8h 9s"));
+        assert!(low_straight < next_straight);
+
+        let straight_flush = variant.to_showdown_hand(&hand!("As 6s 7s 8s 9s"));
+        assert_eq!(Order::Stfl, straight_flush.order());
+
+        // not a straight at all in a full deck
+        assert_eq!(Order::High, Variant::Holdem.to_showdown_hand(&hand!("As 6d 7c 8h 9s")).order());
+    }
+
+    #[test]
+    fn short_deck_flush_beats_full_house() {
+        let variant = Variant::ShortDeck(ShortDeckRules::default());
+        let flush_cards = hand!("As Js 9s 7s 6s");
+        let boat_cards = hand!("Kd Kc Ks Qd Qc");
+        let flush = variant.to_showdown_hand(&flush_cards);
+        let boat = variant.to_showdown_hand(&boat_cards);
+        assert!(flush > boat);
+        assert!(flush.value() > boat.value());
+        assert!(Variant::Holdem.to_showdown_hand(&flush_cards) < Variant::Holdem.to_showdown_hand(&boat_cards));
+
+        // the flush is picked over the boat from the same cards
+        let both = hand!("Ks Kh 9s 9h 9d 7s 6s As");
+        assert_eq!(Order::Boat, Variant::Holdem.to_showdown_hand(&both).order());
+        assert_eq!(Order::Flsh, variant.to_showdown_hand(&both).order());
+    }
+
+    #[test]
+    fn short_deck_trips_beat_straight_house_rule() {
+        let cards = hand!("9s 9h 9d Tc Jd Qh Ks");
+
+        let variant = Variant::ShortDeck(ShortDeckRules::default());
+        assert_eq!(Order::Strt, variant.to_showdown_hand(&cards).order());
+
+        let variant = Variant::ShortDeck(ShortDeckRules { trips_beat_straight: true });
+        let trips = variant.to_showdown_hand(&cards);
+        assert_eq!(Order::Trip, trips.order());
+        let straight = variant.to_showdown_hand(&hand!("As Kd Qc Jh Ts"));
+        assert!(trips > straight);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{rank_set, BuildingHand, Card, Order, Rank, RealizedHand, ShowdownHand, SUITS};
+
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct WildRules {
+    pub wild_ranks: Vec<Rank>, // every card of these ranks is wild, e.g. deuces wild
+    pub bug: bool,             // jokers only complete straights and flushes, otherwise they're aces
+}
+
+impl WildRules {
+    pub fn deuces_wild() -> WildRules {
+        WildRules { wild_ranks: vec![Rank::_2], bug: false }
+    }
+
+    pub fn bug() -> WildRules {
+        WildRules { wild_ranks: vec![], bug: true }
+    }
+}
+
+impl BuildingHand {
+    /// Best hand with every joker and wild rank card standing in for the card that helps most.
+    ///
+    /// The realized hand holds the cards the wilds became, except for five of a kind, which only
+    /// holds the natural cards of its rank.
+    pub fn to_wild_realized_hand(&self, rules: &WildRules) -> RealizedHand {
+        let wild_cards = rules.wild_ranks.iter()
+            .flat_map(|rank| SUITS.iter().map(move |suit| Card::from(rank.clone(), suit.clone()) as u64))
+            .fold(0, |cards, card| cards | card);
+        let naturals = BuildingHand(self.0 & 0x000fffffffffffff & !wild_cards);
+
+        let (full, bug) = if rules.bug {
+            (self.len() - naturals.len(), self.jokers())
+        } else {
+            (self.len() - naturals.len() + self.jokers(), 0)
+        };
+
+        if full + bug == 0 {
+            return naturals.to_realized_hand();
+        }
+
+        // nothing else needs the same card twice, so duplicates only matter here
+        let five = (1..14u32).rev().find(|&rank| {
+            let count = ((naturals.0 >> ((rank - 1) << 2)) & 0xf).count_ones() as usize;
+            let bug = if rank == Rank::_A as u32 { bug } else { 0 };
+            count + full + bug >= 5
+        });
+        if let Some(rank) = five {
+            let cards = naturals.0 & (0xf << ((rank - 1) << 2));
+            return BuildingHand(cards).new_realized_hand(Order::Fivk, rank as u64, 0);
+        }
+
+        // all wild and fewer than five of them, nothing beats aces
+        if naturals.is_empty() {
+            return BuildingHand((0xf >> (4 - full - bug)) << 48).to_realized_hand();
+        }
+
+        let unseen = BuildingHand(0x000fffffffffffff & !naturals.0);
+        let mut best: Option<(ShowdownHand, RealizedHand)> = None;
+
+        for full_cards in unseen.combinations(full) {
+            let rest = BuildingHand(unseen.0 & !full_cards.0);
+            for bug_cards in rest.combinations(bug) {
+                let realized = BuildingHand(naturals.0 | full_cards.0 | bug_cards.0).to_realized_hand();
+
+                // the bug is an ace unless it makes a straight or a flush
+                let aces_only = rank_set(bug_cards.0) & !(1 << (Rank::_A as u32 - 1)) == 0;
+                if !aces_only && ![Order::Strt, Order::Flsh, Order::Stfl].contains(&realized.order()) {
+                    continue;
+                }
+
+                let showdown = realized.to_showdown_hand();
+                best = match best {
+                    Some((best_showdown, _)) if best_showdown < showdown => Some((showdown, realized)),
+                    None => Some((showdown, realized)),
+                    best => best,
+                };
+            }
+        }
+
+        best.map(|(_, realized)| realized).expect("Bug! No card for the wilds to become")
+    }
+
+    pub fn to_wild_showdown_hand(&self, rules: &WildRules) -> ShowdownHand {
+        self.to_wild_realized_hand(rules).to_showdown_hand()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(cards: &[Card], jokers: usize) -> BuildingHand {
+        let mut hand = BuildingHand::new();
+        for card in cards {
+            hand.add_card(card, true);
+        }
+        for _ in 0..jokers {
+            hand.add_joker();
+        }
+        hand
+    }
+
+    #[test]
+    fn joker_makes_five_of_a_kind() {
+        let quads = hand(&[Card::_9d, Card::_9c, Card::_9h, Card::_9s, Card::_Kd], 1);
+        let showdown = quads.to_wild_showdown_hand(&WildRules::default());
+        assert_eq!(Order::Fivk, showdown.order());
+        assert_eq!([Rank::_9 as u8; 5], showdown.ranks());
+
+        let mut royal = hand(&[Card::_As, Card::_Ks, Card::_Qs, Card::_Js, Card::_Ts], 0).to_showdown_hand();
+        assert!(showdown > royal);
+        royal = hand(&[Card::_As, Card::_Ks, Card::_Qs, Card::_Js], 1).to_wild_showdown_hand(&WildRules::default());
+        assert_eq!(Order::Stfl, royal.order());
+        assert_eq!([Rank::_A as u8, Rank::_K as u8, Rank::_Q as u8, Rank::_J as u8, Rank::_T as u8], royal.ranks());
+    }
+
+    #[test]
+    fn joker_picks_best_card() {
+        let pair = hand(&[Card::_Kd, Card::_8c, Card::_4h, Card::_2s], 1).to_wild_realized_hand(&WildRules::default());
+        assert_eq!(Order::Pair, pair.order());
+        assert_eq!(Order::Pair, pair.to_showdown_hand().order());
+        assert_eq!([Rank::_K as u8, Rank::_K as u8, Rank::_8 as u8, Rank::_4 as u8, Rank::_2 as u8], pair.to_showdown_hand().ranks());
+
+        let flush = hand(&[Card::_Kd, Card::_8d, Card::_4d, Card::_2d, Card::_3c, Card::_4c], 1);
+        let showdown = flush.to_wild_showdown_hand(&WildRules::default());
+        assert_eq!(Order::Flsh, showdown.order());
+        assert_eq!([Rank::_A as u8, Rank::_K as u8, Rank::_8 as u8, Rank::_4 as u8, Rank::_2 as u8], showdown.ranks());
+    }
+
+    #[test]
+    fn deuces_wild() {
+        let rules = WildRules::deuces_wild();
+        let trips = hand(&[Card::_2d, Card::_2c, Card::_Kh, Card::_9s, Card::_5d], 0).to_wild_showdown_hand(&rules);
+        assert_eq!(Order::Trip, trips.order());
+        assert_eq!(Rank::_K as u8, trips.ranks()[0]);
+
+        let five = hand(&[Card::_2d, Card::_2c, Card::_2h, Card::_As, Card::_Ad], 0).to_wild_showdown_hand(&rules);
+        assert_eq!(Order::Fivk, five.order());
+
+        let wild_royal = hand(&[Card::_2d, Card::_2c, Card::_2h, Card::_2s, Card::_Ad], 0).to_wild_showdown_hand(&rules);
+        assert_eq!(Order::Fivk, wild_royal.order());
+        assert_eq!([Rank::_A as u8; 5], wild_royal.ranks());
+    }
+
+    #[test]
+    fn bug_is_ace_or_completes_straights_and_flushes() {
+        let rules = WildRules::bug();
+        let ace_high = hand(&[Card::_Kd, Card::_8c, Card::_4h, Card::_Js], 1).to_wild_showdown_hand(&rules);
+        assert_eq!(Order::High, ace_high.order());
+        assert_eq!(Rank::_A as u8, ace_high.ranks()[0]);
+
+        let pair_of_aces = hand(&[Card::_Ad, Card::_8c, Card::_4h, Card::_Js], 1).to_wild_showdown_hand(&rules);
+        assert_eq!(Order::Pair, pair_of_aces.order());
+        assert_eq!(Rank::_A as u8, pair_of_aces.ranks()[0]);
+
+        // a full joker would make trip kings
+        let kings = hand(&[Card::_Kd, Card::_Kc, Card::_4h, Card::_Js], 1);
+        assert_eq!(Order::Trip, kings.to_wild_showdown_hand(&WildRules::default()).order());
+        let kings = kings.to_wild_showdown_hand(&rules);
+        assert_eq!(Order::Pair, kings.order());
+        assert_eq!([Rank::_K as u8, Rank::_K as u8, Rank::_A as u8, Rank::_J as u8, Rank::_4 as u8], kings.ranks());
+
+        let straight = hand(&[Card::_9d, Card::_8c, Card::_7h, Card::_5s], 1).to_wild_showdown_hand(&rules);
+        assert_eq!(Order::Strt, straight.order());
+        assert_eq!(Rank::_9 as u8, straight.ranks()[0]);
+
+        let five_aces = hand(&[Card::_Ad, Card::_Ac, Card::_Ah, Card::_As], 1).to_wild_showdown_hand(&rules);
+        assert_eq!(Order::Fivk, five_aces.order());
+    }
+
+    #[test]
+    fn only_wilds() {
+        let aces = hand(&[], 3).to_wild_realized_hand(&WildRules::default());
+        assert_eq!(Order::Trip, aces.order());
+        assert_eq!(3, aces.to_building_hand().len());
+        assert_eq!([Rank::_A as u8, Rank::_A as u8, Rank::_A as u8, 0, 0], aces.to_showdown_hand().ranks());
+    }
+}
@@ -0,0 +1,153 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn poker(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_poker"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn eval() {
+    let output = poker(&["eval", "AhKh QhJhTh"], "");
+    assert!(output.status.success());
+    assert_eq!("Ah Kh Qh Jh Th: Royal flush (class 1 of 7462)\n", stdout(&output));
+
+    assert_eq!("Ah Kh 7d: Ace high\n", stdout(&poker(&["eval", "Ah", "Kh", "7d"], "")));
+
+    let output = poker(&["eval", "AhKh Ah"], "");
+    assert!(!output.status.success());
+    assert_eq!("[ERROR] Duplicate card: Ah\n", stderr(&output));
+}
+
+#[test]
+fn equity() {
+    let output = poker(&["equity", "AhKh", "QsQd", "--board", "2c7d9h"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        "AhKh: 28.28% equity, 28.28% win, 0.00% tie\nQsQd: 71.72% equity, 71.72% win, 0.00% tie\n",
+        stdout(&output),
+    );
+
+    assert!(!poker(&["equity", "AhKh", "--board", "2c7d9h"], "").status.success());
+    assert!(!poker(&["equity", "AhKh", "QsQd", "--board"], "").status.success());
+
+    let output = poker(&["equity", "AhKh", "QsQd", "--board", "2c7d9h", "--board", "Tc"], "");
+    assert!(!output.status.success());
+    assert!(stderr(&output).starts_with("[ERROR] Repeated --board\n"));
+}
+
+#[test]
+fn batch() {
+    let output = poker(&["batch"], "AsKs\n# comment\n\n7d7c 2s2h 2d\nXx\n");
+    assert!(!output.status.success());
+    assert_eq!("As Ks: Ace high\n7c 7d 2s 2h 2d: Twos full of sevens (class 318 of 7462)\n", stdout(&output));
+    assert_eq!("[ERROR] Invalid cards: Xx (line 5)\n[ERROR] 1 invalid line(s)\n", stderr(&output));
+
+    assert!(poker(&["batch", "-"], "Ad Kd Qd Jd Td\n").status.success());
+    assert!(!poker(&["batch", "no/such/file"], "").status.success());
+}
+
+#[test]
+fn unknown_command() {
+    let output = poker(&["deal"], "");
+    assert!(!output.status.success());
+    assert!(stderr(&output).starts_with("[ERROR] Unknown command: deal\nUsage:"));
+}
+
+#[test]
+fn json_format() {
+    let output = poker(&["eval", "--format", "json", "AhKh QhJhTh"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        "{\"cards\":[\"Ah\",\"Kh\",\"Qh\",\"Jh\",\"Th\"],\"category\":\"straight flush\",\"description\":\"Royal flush\",\"class\":1,\"value\":2416823209}\n",
+        stdout(&output),
+    );
+
+    let output = poker(&["--format=json", "eval", "AhKh Xx"], "");
+    assert!(!output.status.success());
+    assert_eq!(
+        "{\"error\":{\"kind\":\"invalid_card\",\"message\":\"Invalid cards: AhKh Xx\",\"input\":\"AhKh Xx\",\"position\":5}}\n",
+        stdout(&output),
+    );
+
+    let output = poker(&["equity", "AhKh", "QsQd", "--board", "2c7d9h", "--format", "json"], "");
+    assert!(stdout(&output).starts_with("{\"board\":[\"9h\",\"7d\",\"2c\"],\"dead\":[],\"hands\":[{\"hand\":[\"Ah\",\"Kh\"],"));
+    assert!(stdout(&output).contains("\"wins\":710,\"ties\":0,\"boards\":990}]}"));
+
+    // a repeated card is reported in the argument that repeats it
+    let output = poker(&["--format", "json", "equity", "AhKh", "QsQd", "--board", "2c 7d ah"], "");
+    assert!(!output.status.success());
+    assert_eq!(
+        "{\"error\":{\"kind\":\"duplicate_card\",\"message\":\"Duplicate card: Ah\",\"input\":\"2c 7d ah\",\"position\":6}}\n",
+        stdout(&output),
+    );
+
+    let output = poker(&["batch", "--format", "json"], "AsKs\nAsAs\n");
+    assert!(!output.status.success());
+    assert_eq!(
+        concat!(
+            "{\"line\":1,\"hand\":{\"cards\":[\"As\",\"Ks\"],\"category\":\"high card\",\"description\":\"Ace high\",\"class\":null,\"value\":269336576}}\n",
+            "{\"line\":2,\"error\":{\"kind\":\"duplicate_card\",\"message\":\"Duplicate card: As\",\"input\":\"AsAs\",\"position\":2}}\n",
+            "{\"error\":{\"kind\":\"invalid_input\",\"message\":\"1 invalid line(s)\",\"input\":null,\"position\":null}}\n",
+        ),
+        stdout(&output),
+    );
+
+    let output = poker(&["deal", "--format", "json"], "");
+    assert_eq!("{\"error\":{\"kind\":\"usage\",\"message\":\"Unknown command: deal\",\"input\":null,\"position\":null}}\n", stdout(&output));
+    assert!(stderr(&poker(&["eval", "As", "--format", "xml"], "")).starts_with("[ERROR] Unknown format: xml\n"));
+}
+
+#[test]
+fn interactive_best_hands() {
+    let output = stdout(&poker(&[], "AhKh\nQhJh2c\nTh\nquit\n"));
+    assert!(output.contains("Cards input: Qh Jh 2c\nBest hand: Ace high (high card): Ah Kh Qh Jh 2c\n"));
+    assert!(output.contains(concat!(
+        "Summary:\n",
+        "  preflop  Ah Kh     Ace high (high card): Ah Kh\n",
+        "  flop     Qh Jh 2c  Ace high (high card): Ah Kh Qh Jh 2c\n",
+        "  turn     Th        Royal flush (straight flush): Ah Kh Qh Jh Th\n",
+    )));
+}
+
+#[test]
+fn interactive_commands() {
+    let output = stdout(&poker(&[], "AhKh\nQhJh2c\nundo\ndead 2d\n2d9c8c\nshow\nreset\nshow\nquit\n"));
+    assert!(output.contains("Took back the flop: Qh Jh 2c\n"));
+    assert!(output.contains("Add 3 flop card(s):\nDead card: 2d\n"));
+    assert!(!output.contains("Cards input: 2d 9c 8c"));
+    assert!(output.contains(concat!(
+        "Variant: holdem\n",
+        "Streets: preflop 2 (hole), flop 3, turn 1, river 1\n",
+        "Dead cards: 2d\n\n",
+        "Summary:\n",
+        "  preflop  Ah Kh     Ace high (high card): Ah Kh\n",
+    )));
+    assert!(output.contains("New hand\n\nAdd 2 preflop card(s):\nVariant: holdem\nStreets: preflop 2 (hole), flop 3, turn 1, river 1\nDead cards: none\nNo cards dealt\n"));
+
+    // omaha plays exactly two hole cards
+    let output = stdout(&poker(&[], "variant omaha\nAhAd2c3d\nKhQhJh\n"));
+    assert!(output.contains("Playing omaha, new hand\n\nAdd 4 preflop card(s):"));
+    assert!(output.contains("Best hand: Pair of aces (pair): Ah Ad Kh Qh Jh\n"));
+
+    let output = stdout(&poker(&[], "streets third:3:hole fourth:1:hole\nAsAdKs\nKd\n"));
+    assert!(output.contains("Streets: third 3 (hole), fourth 1 (hole), new hand\n"));
+    assert!(output.contains("Best hand: Two pair, aces and kings (two pair): As Ad Ks Kd\n\nSummary:\n"));
+    assert!(output.contains("Hand complete, undo, reset or quit:"));
+    assert!(stdout(&poker(&[], "streets flop\n")).contains("Invalid street, name:cards or name:cards:hole required: flop\n"));
+}
@@ -1,5 +1,3 @@
-use poker;
-
 #[test]
 fn trivial() {
     assert_eq!(2, 2);